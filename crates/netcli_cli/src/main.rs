@@ -0,0 +1,191 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: netcli [--format json|json-pretty|ndjson|csv|table] <platform> <command> [FILE]
+
+Parses captured CLI output read from FILE (or stdin) and prints structured records.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    JsonPretty,
+    Ndjson,
+    Csv,
+    Table,
+}
+
+impl Format {
+    fn from_arg(s: &str) -> Option<Self> {
+        match s {
+            "json" => Some(Self::Json),
+            "json-pretty" => Some(Self::JsonPretty),
+            "ndjson" => Some(Self::Ndjson),
+            "csv" => Some(Self::Csv),
+            "table" => Some(Self::Table),
+            _ => None,
+        }
+    }
+}
+
+struct Args {
+    format: Format,
+    platform: String,
+    command: String,
+    input: Option<String>,
+}
+
+fn parse_args(mut argv: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut format = Format::Json;
+    let mut positional = Vec::new();
+
+    while let Some(arg) = argv.next() {
+        if arg == "--format" || arg == "-f" {
+            let value = argv.next().ok_or("--format requires a value")?;
+            format = Format::from_arg(&value).ok_or_else(|| format!("unknown format: {value}"))?;
+        } else if let Some(value) = arg.strip_prefix("--format=") {
+            format = Format::from_arg(value).ok_or_else(|| format!("unknown format: {value}"))?;
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option: {arg}"));
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let (Some(platform), Some(command)) = (positional.next(), positional.next()) else {
+        return Err("missing <platform> or <command>".into());
+    };
+    let input = positional.next();
+    if positional.next().is_some() {
+        return Err("too many arguments".into());
+    }
+
+    Ok(Args {
+        format,
+        platform,
+        command,
+        input,
+    })
+}
+
+fn read_input(path: Option<&str>) -> std::io::Result<String> {
+    match path {
+        Some("-") | None => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            Ok(buf)
+        }
+        Some(p) => std::fs::read_to_string(p),
+    }
+}
+
+/// Column order for the table format: the template's fields in declaration
+/// order, as in the CSV header, then any other record keys sorted.
+fn columns<'a>(fields: &'a [String], records: &'a [HashMap<String, String>]) -> Vec<&'a str> {
+    let mut cols: Vec<&str> = fields.iter().map(String::as_str).collect();
+    let extra: BTreeSet<&str> = records
+        .iter()
+        .flat_map(|r| r.keys().map(String::as_str))
+        .filter(|k| !fields.iter().any(|f| f == k))
+        .collect();
+    cols.extend(extra);
+    cols
+}
+
+fn render_table(fields: &[String], records: &[HashMap<String, String>]) -> String {
+    let cols = columns(fields, records);
+    let widths: Vec<usize> = cols
+        .iter()
+        .map(|c| {
+            records
+                .iter()
+                .map(|r| r.get(*c).map_or(0, |v| v.chars().count()))
+                .chain(std::iter::once(c.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: &[&str]| -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    let rule: Vec<&str> = rule.iter().map(String::as_str).collect();
+
+    let mut out = line(&cols);
+    out.push_str(&line(&rule));
+    for rec in records {
+        let cells: Vec<&str> = cols
+            .iter()
+            .map(|c| rec.get(*c).map(String::as_str).unwrap_or(""))
+            .collect();
+        out.push_str(&line(&cells));
+    }
+    out
+}
+
+fn run(args: Args) -> ExitCode {
+    let output = match read_input(args.input.as_deref()) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("netcli: cannot read input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let rendered = match args.format {
+        Format::Json | Format::JsonPretty => {
+            let json = netcli_core::parse_command_json(&args.platform, &args.command, &output);
+            let envelope: serde_json::Value = serde_json::from_str(&json).unwrap_or_default();
+            let ok = envelope["ok"] == true;
+            if args.format == Format::JsonPretty {
+                println!("{}", serde_json::to_string_pretty(&envelope).unwrap_or(json));
+            } else {
+                println!("{json}");
+            }
+            return if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+        }
+        Format::Ndjson => {
+            let key = netcli_core::resolve(&args.platform, &args.command).command_key;
+            netcli_core::parse_records_ndjson(&args.platform, &key, &output)
+        }
+        Format::Csv => {
+            let key = netcli_core::resolve(&args.platform, &args.command).command_key;
+            netcli_core::parse_records_csv(&args.platform, &key, &output)
+        }
+        Format::Table => {
+            let key = netcli_core::resolve(&args.platform, &args.command).command_key;
+            netcli_core::prepare(&args.platform, &key).and_then(|template| {
+                let records = template.parse_records(&output, &netcli_core::ParseOptions::default())?;
+                Ok(render_table(template.fields(), &records))
+            })
+        }
+    };
+    let rendered = match rendered {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            eprintln!("netcli: {} ({})", e, e.code());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    print!("{rendered}");
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(args) => run(args),
+        Err(msg) => {
+            eprintln!("netcli: {msg}\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
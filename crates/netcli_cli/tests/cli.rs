@@ -0,0 +1,108 @@
+use std::process::{Command, Output};
+
+const FIXTURE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../netcli_core/tests/fixtures/cisco_ios/show_ip_interface_brief.txt"
+);
+
+fn netcli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_netcli"))
+        .args(args)
+        .output()
+        .expect("failed to run netcli")
+}
+
+fn run_format(format: &str) -> String {
+    let out = netcli(&["--format", format, "cisco_ios", "show ip int br", FIXTURE]);
+    assert!(out.status.success(), "netcli --format {format} failed: {out:?}");
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn format_defaults_to_json_envelope() {
+    let out = netcli(&["cisco_ios", "show ip int br", FIXTURE]);
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "compact json is a single line");
+    let v: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(v["ok"], true);
+    assert_eq!(v["records"].as_array().unwrap().len(), 12);
+}
+
+#[test]
+fn format_json_pretty_is_indented() {
+    let stdout = run_format("json-pretty");
+    assert!(stdout.lines().count() > 1);
+    assert!(stdout.contains("\n  \"ok\": true"));
+    let v: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(v["commandKey"], "show_ip_interface_brief");
+}
+
+#[test]
+fn format_ndjson_emits_one_record_per_line() {
+    let stdout = run_format("ndjson");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 12);
+    for line in &lines {
+        let rec: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(rec["interface"].is_string());
+    }
+    assert!(lines[0].contains("\"interface\":\"GigabitEthernet0/0\""));
+}
+
+#[test]
+fn format_ndjson_renders_list_fields_as_arrays() {
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/../netcli_core/tests/fixtures/cisco_ios/show_version.txt");
+    let out = netcli(&["--format", "ndjson", "cisco_ios", "show version", fixture]);
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let rec: serde_json::Value = serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    assert!(rec["hardware"].is_array(), "List field should be an array: {rec}");
+
+    let text = std::fs::read_to_string(fixture).unwrap();
+    assert_eq!(stdout, netcli_core::parse_records_ndjson("cisco_ios", "show_version", &text).unwrap());
+}
+
+#[test]
+fn format_csv_has_header_and_comma_separated_rows() {
+    let stdout = run_format("csv");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 13, "header plus one row per record");
+    assert_eq!(lines[0], "interface,ip_address,status,proto", "template declaration order");
+    assert_eq!(lines[1], "GigabitEthernet0/0,10.1.1.1,up,up");
+    assert!(stdout.ends_with("\r\n"), "same RFC 4180 rows as parse_records_csv");
+
+    let fixture = std::fs::read_to_string(FIXTURE).unwrap();
+    let library = netcli_core::parse_records_csv("cisco_ios", "show_ip_interface_brief", &fixture).unwrap();
+    assert_eq!(stdout, library);
+}
+
+#[test]
+fn format_table_aligns_columns() {
+    let stdout = run_format("table");
+    let lines: Vec<&str> = stdout.lines().collect();
+    let header: Vec<&str> = lines[0].split_whitespace().collect();
+    assert_eq!(header, ["interface", "ip_address", "status", "proto"], "template declaration order");
+    assert!(lines[1].starts_with("---"));
+    let col = lines[0].find("ip_address").unwrap();
+    for line in &lines[2..] {
+        assert_eq!(&line[col - 2..col], "  ", "column should start at the same offset: {line}");
+        assert_ne!(&line[col..col + 1], " ");
+    }
+}
+
+#[test]
+fn invalid_format_prints_usage_and_exits_2() {
+    let out = netcli(&["--format", "xml", "cisco_ios", "show version", FIXTURE]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("usage: netcli"));
+}
+
+#[test]
+fn parse_failure_exits_nonzero() {
+    let out = netcli(&["--format", "csv", "cisco_ios", "show magic unicorn", FIXTURE]);
+    assert_eq!(out.status.code(), Some(1));
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("TEMPLATE_NOT_FOUND"));
+}
@@ -0,0 +1,345 @@
+// Health checks computed from parsed records.
+
+use std::collections::HashMap;
+
+use crate::parse::list_items;
+
+/// DOM metrics checked by [`transceiver_alarms`]: canonical name, reading
+/// field candidates, and threshold field prefixes, across template dialects.
+const DOM_METRICS: &[(&str, &[&str], &[&str])] = &[
+    (
+        "temperature",
+        &["temperature", "temperature_value"],
+        &["temperature"],
+    ),
+    ("voltage", &["voltage", "voltage_value"], &["voltage"]),
+    (
+        "bias_current",
+        &["bias_current", "laser_bias", "amps_value"],
+        &["bias_current", "amps"],
+    ),
+    ("tx_power", &["tx_power", "tx_value"], &["tx_power", "tx"]),
+    ("rx_power", &["rx_power", "rx_value"], &["rx_power", "rx"]),
+];
+
+fn number(rec: &HashMap<String, String>, fields: &[String]) -> Option<f64> {
+    fields
+        .iter()
+        .find_map(|f| rec.get(f))
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+}
+
+/// Like [`number`], but a `List` value (`[2.2, 2.4]`, one reading per lane)
+/// gives every lane's reading.
+fn lane_numbers(rec: &HashMap<String, String>, fields: &[String]) -> Vec<f64> {
+    let Some(value) = fields.iter().find_map(|f| rec.get(f)) else {
+        return Vec::new();
+    };
+    list_items(value.trim())
+        .into_iter()
+        .filter_map(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+        .collect()
+}
+
+/// Compare DOM readings against their alarm thresholds.
+///
+/// Returns `(port, breaches)` for every record with at least one breach, where
+/// each breach is `<metric>_low` or `<metric>_high` (e.g. `rx_power_low`).
+/// Thresholds are read from `<metric>_alarm_low` / `<metric>_low_alarm` (and
+/// the `_high` equivalents). A multi-lane reading breaches when any lane
+/// does. Missing or non-numeric readings and thresholds (`N/A`, `-inf`) are
+/// skipped.
+pub fn transceiver_alarms(records: &[HashMap<String, String>]) -> Vec<(String, Vec<String>)> {
+    let mut alarms = Vec::new();
+    for rec in records {
+        let port = rec
+            .get("port")
+            .or_else(|| rec.get("interface"))
+            .cloned()
+            .unwrap_or_default();
+
+        let mut breaches = Vec::new();
+        for (metric, readings, prefixes) in DOM_METRICS {
+            let readings: Vec<String> = readings.iter().map(|s| s.to_string()).collect();
+            let lanes = lane_numbers(rec, &readings);
+            if lanes.is_empty() {
+                continue;
+            }
+            for side in ["low", "high"] {
+                let thresholds: Vec<String> = prefixes
+                    .iter()
+                    .flat_map(|p| [format!("{p}_alarm_{side}"), format!("{p}_{side}_alarm")])
+                    .collect();
+                let Some(limit) = number(rec, &thresholds) else {
+                    continue;
+                };
+                let breached = match side {
+                    "low" => lanes.iter().any(|&v| v < limit),
+                    _ => lanes.iter().any(|&v| v > limit),
+                };
+                if breached {
+                    breaches.push(format!("{metric}_{side}"));
+                }
+            }
+        }
+
+        if !breaches.is_empty() {
+            alarms.push((port, breaches));
+        }
+    }
+    alarms
+}
+
+/// Classify a temperature sensor reading against its thresholds.
+///
+/// Reads `temperature`, `high_warning` and `high_critical` (as produced by the
+/// DNOS `show system hardware temperature` template) and returns `"critical"`
+/// or `"warning"` once the reading reaches the respective threshold, `"ok"`
+/// below both, and `"unknown"` when the reading is missing or not a finite
+/// number (`NaN`, `inf`). Missing thresholds are ignored.
+pub fn temperature_state(record: &HashMap<String, String>) -> &'static str {
+    let field = |name: &str| {
+        record.get(name).and_then(|v| v.trim().parse::<f64>().ok()).filter(|v| v.is_finite())
+    };
+    let Some(reading) = field("temperature") else {
+        return "unknown";
+    };
+    if field("high_critical").is_some_and(|limit| reading >= limit) {
+        "critical"
+    } else if field("high_warning").is_some_and(|limit| reading >= limit) {
+        "warning"
+    } else {
+        "ok"
+    }
+}
+
+/// Map a vendor sensor status word onto the [`temperature_state`] scale.
+///
+/// `ok`/`normal`/`green` -> `"ok"`, `warning`/`minor`/`yellow` -> `"warning"`,
+/// `critical`/`major`/`red`/`shutdown` -> `"critical"` (case-insensitive);
+/// anything else is `"unknown"`.
+pub fn sensor_state(status: &str) -> &'static str {
+    match status.trim().to_ascii_lowercase().as_str() {
+        "ok" | "normal" | "green" => "ok",
+        "warning" | "minor" | "yellow" => "warning",
+        "critical" | "major" | "red" | "shutdown" => "critical",
+        _ => "unknown",
+    }
+}
+
+/// Flag duplex/speed settings that usually mean a negotiation mismatch, from
+/// `show interfaces status` records (`port`, `duplex`, `speed`).
+///
+/// Returns `(port, reason)` pairs, where reason is:
+///
+/// - `half_duplex_gigabit`: half duplex, forced or negotiated (`half`,
+///   `a-half`), at 1000 Mb/s or faster, which Ethernet never negotiates;
+/// - `mixed_negotiation`: one of duplex and speed was auto-negotiated (`a-`)
+///   while the other is forced, the classic one-side-hardcoded mismatch.
+///
+/// Ports still showing plain `auto` have no link and are never flagged.
+pub fn duplex_speed_anomalies(records: &[HashMap<String, String>]) -> Vec<(String, String)> {
+    let mut anomalies = Vec::new();
+    for rec in records {
+        let field = |name: &str| rec.get(name).map(|v| v.trim().to_ascii_lowercase()).unwrap_or_default();
+        let (duplex, speed) = (field("duplex"), field("speed"));
+        let port = rec.get("port").cloned().unwrap_or_default();
+
+        let duplex_auto = duplex.starts_with("a-");
+        let speed_auto = speed.starts_with("a-");
+        let is_forced = |v: &str, auto: bool| !auto && !v.is_empty() && v != "auto";
+
+        let mbps = speed_mbps(speed.trim_start_matches("a-"));
+        if duplex.trim_start_matches("a-") == "half" && mbps.is_some_and(|m| m >= 1000) {
+            anomalies.push((port.clone(), "half_duplex_gigabit".to_string()));
+        }
+        if (duplex_auto && is_forced(&speed, speed_auto)) || (speed_auto && is_forced(&duplex, duplex_auto)) {
+            anomalies.push((port, "mixed_negotiation".to_string()));
+        }
+    }
+    anomalies
+}
+
+/// Link speed in Mb/s from a status-table speed (`100`, `1000`, `10G`).
+fn speed_mbps(speed: &str) -> Option<u64> {
+    match speed.strip_suffix('g') {
+        Some(gig) => gig.parse::<u64>().ok().map(|g| g * 1000),
+        None => speed.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn duplex_speed_anomalies_flag_half_gigabit_and_mixed_negotiation() {
+        let status = |port, duplex, speed| rec(&[("port", port), ("duplex", duplex), ("speed", speed)]);
+        let records = vec![
+            status("Gi1/0/1", "a-full", "a-1000"),
+            status("Gi1/0/2", "half", "1000"),
+            status("Gi1/0/3", "a-half", "100"),
+            status("Gi1/0/4", "full", "a-1000"),
+            status("Gi1/0/5", "auto", "auto"),
+            status("Te1/1/1", "a-half", "a-10G"),
+        ];
+
+        assert_eq!(
+            duplex_speed_anomalies(&records),
+            [
+                ("Gi1/0/2".to_string(), "half_duplex_gigabit".to_string()),
+                ("Gi1/0/3".to_string(), "mixed_negotiation".to_string()),
+                ("Gi1/0/4".to_string(), "mixed_negotiation".to_string()),
+                ("Te1/1/1".to_string(), "half_duplex_gigabit".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rx_power_below_low_alarm_is_reported() {
+        let records = vec![
+            rec(&[
+                ("port", "Te0/0/0"),
+                ("rx_power", "-14.2"),
+                ("rx_power_alarm_low", "-12.0"),
+                ("rx_power_alarm_high", "2.0"),
+                ("tx_power", "-2.1"),
+                ("tx_power_alarm_low", "-9.0"),
+            ]),
+            rec(&[
+                ("port", "Te0/0/1"),
+                ("rx_power", "-3.4"),
+                ("rx_power_alarm_low", "-12.0"),
+            ]),
+        ];
+
+        let alarms = transceiver_alarms(&records);
+        assert_eq!(
+            alarms,
+            vec![("Te0/0/0".to_string(), vec!["rx_power_low".to_string()])]
+        );
+    }
+
+    #[test]
+    fn nxos_style_fields_and_high_breach() {
+        let records = vec![rec(&[
+            ("interface", "Ethernet1/1"),
+            ("temperature_value", "78.5"),
+            ("temperature_alarm_high", "75.0"),
+            ("temperature_alarm_low", "-5.0"),
+            ("amps_value", "1.0"),
+            ("amps_alarm_low", "2.0"),
+        ])];
+
+        let alarms = transceiver_alarms(&records);
+        assert_eq!(alarms.len(), 1);
+        assert_eq!(alarms[0].0, "Ethernet1/1");
+        assert_eq!(alarms[0].1, vec!["temperature_high", "bias_current_low"]);
+    }
+
+    #[test]
+    fn any_lane_of_a_list_reading_can_breach() {
+        let records = vec![
+            rec(&[
+                ("interface", "ge100-0/0/3"),
+                ("rx_power", "[2.4, -20.1, 2.9, 3.0]"),
+                ("rx_power_low_alarm", "-14.6"),
+            ]),
+            rec(&[
+                ("interface", "ge100-0/0/5"),
+                ("rx_power", "[]"),
+                ("rx_power_low_alarm", "-14.6"),
+            ]),
+        ];
+
+        let alarms = transceiver_alarms(&records);
+        assert_eq!(
+            alarms,
+            vec![("ge100-0/0/3".to_string(), vec!["rx_power_low".to_string()])]
+        );
+    }
+
+    #[test]
+    fn missing_or_non_numeric_thresholds_are_tolerated() {
+        let records = vec![
+            rec(&[("port", "Gi1/0/1"), ("rx_power", "-40.0")]),
+            rec(&[
+                ("port", "Gi1/0/2"),
+                ("rx_power", "-40.0"),
+                ("rx_power_alarm_low", "N/A"),
+            ]),
+            rec(&[
+                ("port", "Gi1/0/3"),
+                ("rx_power", "N/A"),
+                ("rx_power_alarm_low", "-12.0"),
+            ]),
+            rec(&[
+                ("port", "Gi1/0/4"),
+                ("rx_power", "-40.0"),
+                ("rx_power_alarm_high", "-inf"),
+            ]),
+            rec(&[
+                ("port", "Gi1/0/5"),
+                ("rx_power", "-inf"),
+                ("rx_power_alarm_low", "-12.0"),
+            ]),
+        ];
+        assert!(transceiver_alarms(&records).is_empty());
+    }
+
+    #[test]
+    fn temperature_state_near_warning_threshold() {
+        let sensor = |t: &str| {
+            rec(&[
+                ("sensor_name", "TEMP_OP2_ENV"),
+                ("temperature", t),
+                ("high_warning", "70"),
+                ("high_critical", "75"),
+            ])
+        };
+        assert_eq!(temperature_state(&sensor("69.5")), "ok");
+        assert_eq!(temperature_state(&sensor("70.0")), "warning");
+        assert_eq!(temperature_state(&sensor("74.9")), "warning");
+        assert_eq!(temperature_state(&sensor("75.0")), "critical");
+    }
+
+    #[test]
+    fn temperature_state_non_finite_reading_is_unknown() {
+        for t in ["NaN", "inf", "-inf"] {
+            let sensor = rec(&[("temperature", t), ("high_warning", "70"), ("high_critical", "75")]);
+            assert_eq!(temperature_state(&sensor), "unknown", "{t}");
+        }
+    }
+
+    #[test]
+    fn sensor_state_vocabulary() {
+        assert_eq!(sensor_state("ok"), "ok");
+        assert_eq!(sensor_state("Normal"), "ok");
+        assert_eq!(sensor_state("GREEN"), "ok");
+        assert_eq!(sensor_state("warning"), "warning");
+        assert_eq!(sensor_state("Minor"), "warning");
+        assert_eq!(sensor_state("critical"), "critical");
+        assert_eq!(sensor_state("RED"), "critical");
+        assert_eq!(sensor_state("faulty"), "unknown");
+        assert_eq!(sensor_state(""), "unknown");
+    }
+
+    #[test]
+    fn temperature_state_tolerates_missing_values() {
+        assert_eq!(temperature_state(&rec(&[("temperature", "N/A")])), "unknown");
+        assert_eq!(temperature_state(&rec(&[("temperature", "40.0")])), "ok");
+        assert_eq!(
+            temperature_state(&rec(&[("temperature", "96.0"), ("high_warning", "N/A"), ("high_critical", "95")])),
+            "critical"
+        );
+    }
+}
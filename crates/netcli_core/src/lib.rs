@@ -1,7 +1,30 @@
+pub mod checks;
 pub mod commands;
 pub mod normalize;
 pub mod parse;
 pub mod platform;
+pub(crate) mod preprocess;
+pub mod records;
 pub(crate) mod registry;
+pub(crate) mod typed;
 
-pub use parse::{parse_command_json, parse_command_records, parse_json, parse_records, ParseError};
+pub use checks::{duplex_speed_anomalies, sensor_state, temperature_state, transceiver_alarms};
+pub use parse::{
+    parse_command_json, parse_command_json_with_options, parse_command_records, parse_json,
+    parse_json_with_options, parse_records, parse_records_csv, parse_records_csv_with_options, parse_records_meta,
+    parse_records_ndjson, parse_records_opts, parse_records_typed,
+    parse_records_with, parse_records_with_options, parse_single, parse_single_with_options, prepare, run_template,
+    run_template_json, validate_template, ParseError, ParseMeta, ParseOptions, ParseOptionsBuilder,
+    PreparedTemplate, TemplateInfo, TemplateValue, DEFAULT_CSV_LIST_DELIMITER,
+    ENVELOPE_SCHEMA_VERSION,
+};
+#[cfg(feature = "mpsc")]
+pub use parse::{parse_to_channel, parse_to_channel_with_options};
+pub use platform::{
+    canonical_slug, is_alias, register_platform, register_platform_alias, Platform, PlatformProvider,
+};
+pub use records::{counter_deltas, join_records, merge_records, records_to_prometheus, Records};
+pub use registry::{
+    duplicate_registry_keys, list_commands_for_platform, list_supported_platforms, registry_stats, resolve,
+    RegistryStats, Resolution,
+};
@@ -1 +1,675 @@
-// Field normalization — will be rebuilt when canonical schema mappings are defined.
+// Field normalization — canonical value forms shared across platforms.
+
+use std::collections::HashMap;
+
+use crate::checks::{sensor_state, temperature_state};
+
+/// Normalize a MAC address to lowercase, colon-separated `aa:bb:cc:dd:ee:ff`.
+///
+/// Accepts Cisco dotted (`0026.9876.1234`), colon (`00:26:98:76:12:34`) and
+/// hyphen (`00-26-98-76-12-34`) notation. Returns `None` if the input is not
+/// a 48-bit MAC in one of those forms.
+pub fn normalize_mac(s: &str) -> Option<String> {
+    let s = s.trim();
+    let groups: Vec<&str> = if s.contains('.') {
+        s.split('.').collect()
+    } else if s.contains(':') {
+        s.split(':').collect()
+    } else {
+        s.split('-').collect()
+    };
+
+    let group_len = match groups.len() {
+        3 => 4,
+        6 => 2,
+        _ => return None,
+    };
+    if groups
+        .iter()
+        .any(|g| g.len() != group_len || !g.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return None;
+    }
+
+    let hex = groups.concat().to_ascii_lowercase();
+    let octets: Vec<&str> = (0..12).step_by(2).map(|i| &hex[i..i + 2]).collect();
+    Some(octets.join(":"))
+}
+
+fn is_mac_field(name: &str) -> bool {
+    name == "mac" || name.ends_with("_mac") || name.starts_with("mac_") || name.contains("_mac_")
+}
+
+/// Rewrite MAC-valued fields of a record into canonical form.
+///
+/// A field is treated as a MAC when its name is `mac` or contains a `mac`
+/// word (`mac_address`, `sys_mac`, `chassis_mac`, ...). Values that do not
+/// parse as a MAC are left untouched.
+pub fn normalize_mac_fields(record: &mut HashMap<String, String>) {
+    for (name, value) in record.iter_mut() {
+        if !is_mac_field(name) {
+            continue;
+        }
+        if let Some(mac) = normalize_mac(value) {
+            *value = mac;
+        }
+    }
+}
+
+/// Whether `s` matches the coercible-number grammar `-?[0-9]+(\.[0-9]+)?`.
+fn is_plain_number(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let (int, frac) = match digits.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (digits, None),
+    };
+    let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    all_digits(int) && frac.into_iter().all(all_digits)
+}
+
+/// Parse a field value as a JSON number, independent of locale.
+///
+/// Only `-?[0-9]+(\.[0-9]+)?` is accepted: ASCII digits, an optional leading
+/// `-` and at most one `.` as the decimal separator. Anything else — grouping
+/// or decimal commas (`1.234,56`), units or percent signs (`200Gbps`,
+/// `6.60%`), surrounding whitespace — is not a number, so the value stays a
+/// string. Integers that overflow 64 bits are not numbers either.
+pub fn coercible_number(s: &str) -> Option<serde_json::Number> {
+    if !is_plain_number(s) {
+        return None;
+    }
+    if s.contains('.') {
+        return s.parse::<f64>().ok().and_then(serde_json::Number::from_f64);
+    }
+    match s.parse::<i64>() {
+        Ok(n) => Some(n.into()),
+        Err(_) => s.parse::<u64>().ok().map(Into::into),
+    }
+}
+
+/// Split a value into its numeric part and unit suffix, e.g. `200Gbps` ->
+/// `("200", "Gbps")` and `6.60 %` -> `("6.60", "%")`. Whitespace between the
+/// two is dropped. `None` when the leading part does not match the
+/// [`coercible_number`] grammar or there is no suffix starting with a letter
+/// or `%`.
+pub fn strip_units(s: &str) -> Option<(&str, &str)> {
+    let end = s
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (c == '-' && i == 0)))
+        .map(|(i, _)| i)?;
+    let (number, unit) = (&s[..end], s[end..].trim_start());
+    let is_unit = unit.starts_with(|c: char| c.is_alphabetic() || c == '%');
+    (is_plain_number(number) && is_unit).then_some((number, unit))
+}
+
+/// Parse a counter that may be printed with a decimal SI suffix, e.g. `1.2M`
+/// -> `1200000`. `K`, `M`, `G` and `T` (either case) scale by powers of 1000;
+/// a plain value must be a non-negative integer. Digits finer than one unit
+/// are truncated (`1.0005K` -> `1000`). `None` for anything else or on
+/// overflow.
+pub fn parse_si_number(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (digits, scale) = match s.as_bytes().last()?.to_ascii_uppercase() {
+        b'K' => (&s[..s.len() - 1], 1_000u64),
+        b'M' => (&s[..s.len() - 1], 1_000_000),
+        b'G' => (&s[..s.len() - 1], 1_000_000_000),
+        b'T' => (&s[..s.len() - 1], 1_000_000_000_000),
+        _ => (s, 1),
+    };
+    if digits.starts_with('-') || !is_plain_number(digits) {
+        return None;
+    }
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if scale == 1 && !frac.is_empty() {
+        return None;
+    }
+
+    let whole = int.parse::<u64>().ok()?.checked_mul(scale)?;
+    // Only as many fraction digits as the scale can hold matter.
+    let places = scale.ilog10() as usize;
+    let frac = &frac[..frac.len().min(places)];
+    let part = match frac {
+        "" => 0,
+        f => f.parse::<u64>().ok()? * 10u64.pow((places - f.len()) as u32),
+    };
+    whole.checked_add(part)
+}
+
+/// Parse an interface bandwidth such as `1000000 Kbit` into bits per second.
+/// `bit`, `Kbit`, `Mbit` and `Gbit` are accepted in any case; `None` for
+/// other units or a value [`parse_si_number`] rejects.
+pub fn parse_bandwidth_bps(s: &str) -> Option<u64> {
+    let (number, unit) = strip_units(s.trim())?;
+    let suffix = match unit.to_ascii_lowercase().as_str() {
+        "bit" => "",
+        "kbit" => "K",
+        "mbit" => "M",
+        "gbit" => "G",
+        _ => return None,
+    };
+    parse_si_number(&format!("{number}{suffix}"))
+}
+
+/// LLDP system capability codes (IEEE 802.1AB), as printed in the legend of
+/// `show lldp neighbors`.
+const LLDP_CAPABILITY_CODES: &[(char, &str)] = &[
+    ('O', "Other"),
+    ('P', "Repeater"),
+    ('B', "Bridge"),
+    ('W', "WLAN Access Point"),
+    ('R', "Router"),
+    ('T', "Telephone"),
+    ('C', "DOCSIS Cable Device"),
+    ('S', "Station"),
+];
+
+/// CDP capability codes, as printed in the legend of `show cdp neighbors`.
+/// Case matters: `r` is Repeater, `R` is Router.
+const CDP_CAPABILITY_CODES: &[(char, &str)] = &[
+    ('R', "Router"),
+    ('T', "Trans-Bridge"),
+    ('B', "Source-Route-Bridge"),
+    ('S', "Switch"),
+    ('H', "Host"),
+    ('I', "IGMP"),
+    ('r', "Repeater"),
+    ('P', "Phone"),
+    ('D', "Remote"),
+    ('C', "CVTA"),
+    ('M', "Two-port Mac Relay"),
+];
+
+/// Expand LLDP capability codes into full names, e.g. `R B` -> `["Router",
+/// "Bridge"]`.
+///
+/// Codes may be separated by whitespace or commas, or run together (`BR`).
+/// Full names already spelled out are kept (in canonical case), and unknown
+/// codes are passed through unchanged rather than dropped.
+pub fn decode_lldp_capabilities(codes: &str) -> Vec<String> {
+    decode_capabilities(LLDP_CAPABILITY_CODES, codes)
+}
+
+/// Expand CDP capability codes into full names, e.g. `R S I` -> `["Router",
+/// "Switch", "IGMP"]`. Same input rules as [`decode_lldp_capabilities`].
+pub fn decode_cdp_capabilities(codes: &str) -> Vec<String> {
+    decode_capabilities(CDP_CAPABILITY_CODES, codes)
+}
+
+fn decode_capabilities(legend: &[(char, &'static str)], codes: &str) -> Vec<String> {
+    let name_of = |c: char| legend.iter().find(|(code, _)| *code == c).map(|(_, name)| *name);
+
+    let mut names = Vec::new();
+    for token in codes.split(|c: char| c.is_whitespace() || c == ',').filter(|t| !t.is_empty()) {
+        if let Some((_, name)) = legend.iter().find(|(_, n)| n.eq_ignore_ascii_case(token)) {
+            names.push(name.to_string());
+        } else if token.chars().all(|c| name_of(c).is_some()) {
+            names.extend(token.chars().filter_map(name_of).map(String::from));
+        } else {
+            names.push(token.to_string());
+        }
+    }
+    names
+}
+
+/// Full interface type names and the shortest abbreviation accepted for each.
+/// An abbreviation resolves to the first entry whose full name it prefixes.
+const INTERFACE_TYPES: &[(&str, &str)] = &[
+    ("FastEthernet", "fa"),
+    ("GigabitEthernet", "gi"),
+    ("TenGigabitEthernet", "te"),
+    ("TwoGigabitEthernet", "tw"),
+    ("TwentyFiveGigE", "twe"),
+    ("FortyGigabitEthernet", "fo"),
+    ("HundredGigE", "hu"),
+    ("Ethernet", "et"),
+    ("Port-channel", "po"),
+    ("Loopback", "lo"),
+    ("Vlan", "vl"),
+    ("Tunnel", "tu"),
+];
+
+/// Expand an abbreviated interface name to its full form, so that the short
+/// names in tabular output (`Gi0/0/1`, `Te1/1`) match the long names in detail
+/// output (`GigabitEthernet0/0/1`, `TenGigabitEthernet1/1`).
+///
+/// Matching on the type prefix is case-insensitive. Names with an unknown
+/// prefix or no slot number are returned trimmed but otherwise unchanged.
+pub fn normalize_interface_name(s: &str) -> String {
+    let s = s.trim();
+    let Some(split) = s.find(|c: char| c.is_ascii_digit()) else {
+        return s.to_string();
+    };
+    let (prefix, number) = (s[..split].trim_end(), &s[split..]);
+    let lower = prefix.to_ascii_lowercase();
+    if lower.is_empty() {
+        return s.to_string();
+    }
+
+    INTERFACE_TYPES
+        .iter()
+        .find(|(full, min)| lower.starts_with(min) && full.to_ascii_lowercase().starts_with(&lower))
+        .map(|(full, _)| format!("{full}{number}"))
+        .unwrap_or_else(|| s.to_string())
+}
+
+/// Convert a spelled-out uptime into seconds.
+///
+/// Accepts `<n> <unit>` pairs separated by commas and/or `and`, as printed by
+/// `show version` (`2 weeks, 3 days, 12 hours and 34 minutes`). Units are
+/// years (365 days), weeks, days, hours, minutes and seconds, singular or
+/// plural. Returns `None` for anything else.
+pub fn parse_uptime_seconds(s: &str) -> Option<u64> {
+    let tokens: Vec<&str> = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty() && *t != "and")
+        .collect();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let mut total: u64 = 0;
+    for pair in tokens.chunks(2) {
+        let [n, unit] = pair else {
+            return None;
+        };
+        let n: u64 = n.parse().ok()?;
+        let unit = match unit.to_ascii_lowercase().trim_end_matches('s') {
+            "year" => 365 * 86_400,
+            "week" => 7 * 86_400,
+            "day" => 86_400,
+            "hour" => 3_600,
+            "minute" => 60,
+            "second" => 1,
+            _ => return None,
+        };
+        total = total.checked_add(n.checked_mul(unit)?)?;
+    }
+    Some(total)
+}
+
+/// Rate window IOS and NX-OS use when no `load-interval` is configured.
+pub const DEFAULT_LOAD_INTERVAL_SECONDS: u64 = 300;
+
+/// Apply command-specific normalization to parsed records.
+///
+/// Currently this adds derived fields; existing fields are left as parsed:
+///
+/// - `show_system_hardware_temperature`: `temperature_state`
+///   (`ok`/`warning`/`critical`/`unknown`, see [`temperature_state`]).
+/// - `show_environment_temperature`: `temperature_state` from the sensor's
+///   reported `state` (see [`sensor_state`]), on per-sensor records.
+/// - `show_version`: `uptime_seconds` when `uptime` parses with
+///   [`parse_uptime_seconds`].
+/// - `show_interfaces` / `show_interface`: `bandwidth_bps` when `bandwidth`
+///   parses with [`parse_bandwidth_bps`], and `load_interval_seconds`, the
+///   window the rate counters average over (from `load_interval`, else
+///   [`DEFAULT_LOAD_INTERVAL_SECONDS`] when the template has a `load_interval`
+///   field but the output printed none).
+/// - `show_route_summary` / `show_ip_route_summary`: `prefix_count`,
+///   `path_count` and `total`, see [`normalize_route_summary`].
+///
+/// Normalizing is idempotent: `normalize(key, normalize(key, recs))` equals
+/// `normalize(key, recs)`, so every rule must accept its own output.
+pub fn normalize(
+    command_key: &str,
+    mut records: Vec<HashMap<String, String>>,
+) -> Vec<HashMap<String, String>> {
+    match command_key {
+        "show_system_hardware_temperature" => {
+            for rec in &mut records {
+                let state = temperature_state(rec);
+                rec.insert("temperature_state".into(), state.into());
+            }
+        }
+        "show_environment_temperature" => {
+            for rec in &mut records {
+                let Some(state) = rec.get("state").filter(|s| !s.is_empty()) else {
+                    continue;
+                };
+                let state = sensor_state(state);
+                rec.insert("temperature_state".into(), state.into());
+            }
+        }
+        "show_version" => {
+            for rec in &mut records {
+                if let Some(secs) = rec.get("uptime").and_then(|u| parse_uptime_seconds(u)) {
+                    rec.insert("uptime_seconds".into(), secs.to_string());
+                }
+            }
+        }
+        "show_interfaces" | "show_interface" => {
+            for rec in &mut records {
+                if let Some(bps) = rec.get("bandwidth").and_then(|b| parse_bandwidth_bps(b)) {
+                    rec.insert("bandwidth_bps".into(), bps.to_string());
+                }
+                // Only templates that capture a load interval get the default;
+                // others (Junos, EOS, ...) never report one.
+                let Some(interval) = rec.get("load_interval") else {
+                    continue;
+                };
+                let secs = parse_uptime_seconds(interval).unwrap_or(DEFAULT_LOAD_INTERVAL_SECONDS);
+                rec.insert("load_interval_seconds".into(), secs.to_string());
+            }
+        }
+        "show_route_summary" | "show_ip_route_summary" => {
+            records = normalize_route_summary(records);
+        }
+        _ => {}
+    }
+    records
+}
+
+/// Give route-summary rows from any platform the same counters:
+///
+/// - `prefix_count`: `routes` (DNOS) or `networks + subnets` (IOS/IOS-XE);
+/// - `path_count`: `paths` where the platform reports it, else empty;
+/// - `total`: `"true"` on the summary row, `"false"` on per-protocol rows.
+///
+/// When the output has no `Total` row, one is appended whose counts are the
+/// sum of the per-protocol rows (`path_count` stays empty unless every row
+/// has one).
+pub fn normalize_route_summary(mut records: Vec<HashMap<String, String>>) -> Vec<HashMap<String, String>> {
+    fn count(rec: &HashMap<String, String>, field: &str) -> Option<u64> {
+        rec.get(field).and_then(|v| v.parse().ok())
+    }
+    fn as_field(n: Option<u64>) -> String {
+        n.map(|n| n.to_string()).unwrap_or_default()
+    }
+
+    for rec in &mut records {
+        // Falling back to the fields this function writes keeps a second
+        // pass from blanking the synthesized Total row.
+        let prefixes = count(rec, "routes")
+            .or_else(|| Some(count(rec, "networks")? + count(rec, "subnets")?))
+            .or_else(|| count(rec, "prefix_count"));
+        let paths = count(rec, "paths").or_else(|| count(rec, "path_count"));
+        let is_total = rec
+            .get("route_source")
+            .is_some_and(|s| s.eq_ignore_ascii_case("total"));
+
+        rec.insert("prefix_count".into(), as_field(prefixes));
+        rec.insert("path_count".into(), as_field(paths));
+        rec.insert("total".into(), is_total.to_string());
+    }
+
+    if !records.is_empty() && !records.iter().any(|r| r["total"] == "true") {
+        let sum = |field: &str| -> Option<u64> { records.iter().map(|r| count(r, field)).sum() };
+        let (prefixes, paths) = (sum("prefix_count"), sum("path_count"));
+        records.push(HashMap::from([
+            ("route_source".into(), "Total".into()),
+            ("prefix_count".into(), as_field(prefixes)),
+            ("path_count".into(), as_field(paths)),
+            ("total".into(), "true".into()),
+        ]));
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(fields: &[(&str, &str)]) -> HashMap<String, String> {
+        fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn route_summary_synthesizes_missing_total() {
+        let recs = normalize(
+            "show_route_summary",
+            vec![
+                row(&[("route_source", "connected"), ("routes", "12"), ("paths", "12")]),
+                row(&[("route_source", "bgp"), ("routes", "1250"), ("paths", "2500")]),
+            ],
+        );
+        assert_eq!(recs.len(), 3);
+        assert_eq!(recs[1]["prefix_count"], "1250");
+        assert_eq!(recs[1]["total"], "false");
+        assert_eq!(recs[2]["route_source"], "Total");
+        assert_eq!(recs[2]["prefix_count"], "1262");
+        assert_eq!(recs[2]["path_count"], "2512");
+    }
+
+    #[test]
+    fn route_summary_ios_has_no_path_count() {
+        let recs = normalize(
+            "show_ip_route_summary",
+            vec![
+                row(&[("route_source", "connected"), ("networks", "8"), ("subnets", "12")]),
+                row(&[("route_source", "Total"), ("networks", "8"), ("subnets", "12")]),
+            ],
+        );
+        assert_eq!(recs.len(), 2, "device total is kept, not duplicated");
+        assert_eq!(recs[0]["prefix_count"], "20");
+        assert_eq!(recs[0]["path_count"], "");
+        assert_eq!(recs[1]["total"], "true");
+    }
+
+    #[test]
+    fn si_numbers_expand_each_suffix() {
+        assert_eq!(parse_si_number("1200000"), Some(1_200_000));
+        assert_eq!(parse_si_number("1.2M"), parse_si_number("1200000"));
+        assert_eq!(parse_si_number("15K"), Some(15_000));
+        assert_eq!(parse_si_number("3.4G"), Some(3_400_000_000));
+        assert_eq!(parse_si_number("2.25T"), Some(2_250_000_000_000));
+        assert_eq!(parse_si_number(" 7k "), Some(7_000), "lowercase and padding");
+        assert_eq!(parse_si_number("1.0005K"), Some(1_000), "sub-unit digits truncate");
+    }
+
+    #[test]
+    fn si_numbers_reject_non_counters() {
+        for s in ["", "M", "1.5", "-3K", "1,2M", "1.2X", "12 Mbps", "20000000T"] {
+            assert_eq!(parse_si_number(s), None, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn bandwidth_units_scale_to_bps() {
+        assert_eq!(parse_bandwidth_bps("1000000 Kbit"), Some(1_000_000_000));
+        assert_eq!(parse_bandwidth_bps("10000000 kbit"), Some(10_000_000_000));
+        assert_eq!(parse_bandwidth_bps("100 Mbit"), Some(100_000_000));
+        assert_eq!(parse_bandwidth_bps("40 Gbit"), Some(40_000_000_000));
+        assert_eq!(parse_bandwidth_bps("1544 Kbit"), Some(1_544_000));
+        assert_eq!(parse_bandwidth_bps("9600 bit"), Some(9_600));
+        assert_eq!(parse_bandwidth_bps("1000 usec"), None);
+        assert_eq!(parse_bandwidth_bps("1000000"), None);
+    }
+
+    #[test]
+    fn capabilities_expand_codes() {
+        assert_eq!(decode_lldp_capabilities("R B"), ["Router", "Bridge"]);
+        assert_eq!(decode_lldp_capabilities("B,R"), ["Bridge", "Router"]);
+        assert_eq!(decode_lldp_capabilities("BR"), ["Bridge", "Router"], "NX-OS runs codes together");
+        assert_eq!(decode_lldp_capabilities("router, Bridge"), ["Router", "Bridge"]);
+        assert!(decode_lldp_capabilities("  ").is_empty());
+    }
+
+    #[test]
+    fn capabilities_keep_unknown_codes() {
+        assert_eq!(decode_lldp_capabilities("R X"), ["Router", "X"]);
+        assert_eq!(decode_lldp_capabilities("BX"), ["BX"], "not every letter is a code");
+        assert_eq!(decode_lldp_capabilities("r"), ["r"], "LLDP codes are upper case");
+    }
+
+    #[test]
+    fn cdp_capabilities_use_the_cdp_legend() {
+        assert_eq!(decode_cdp_capabilities("R S I"), ["Router", "Switch", "IGMP"]);
+        assert_eq!(decode_cdp_capabilities("r"), ["Repeater"]);
+        assert_eq!(decode_cdp_capabilities("T B"), ["Trans-Bridge", "Source-Route-Bridge"]);
+        assert_eq!(decode_cdp_capabilities("H P"), ["Host", "Phone"]);
+    }
+
+    #[test]
+    fn mac_cisco_dotted() {
+        assert_eq!(normalize_mac("0026.9876.1234").as_deref(), Some("00:26:98:76:12:34"));
+    }
+
+    #[test]
+    fn mac_colon_separated() {
+        assert_eq!(normalize_mac("00:26:98:76:12:34").as_deref(), Some("00:26:98:76:12:34"));
+        assert_eq!(normalize_mac("84:40:76:D9:0E:4E").as_deref(), Some("84:40:76:d9:0e:4e"));
+    }
+
+    #[test]
+    fn mac_hyphen_separated() {
+        assert_eq!(normalize_mac("00-26-98-76-12-34").as_deref(), Some("00:26:98:76:12:34"));
+    }
+
+    #[test]
+    fn mac_malformed_returns_none() {
+        assert_eq!(normalize_mac(""), None);
+        assert_eq!(normalize_mac("0026.9876"), None);
+        assert_eq!(normalize_mac("00:26:98:76:12:3g"), None);
+        assert_eq!(normalize_mac("002698761234"), None);
+        assert_eq!(normalize_mac("0026.98761.234"), None);
+    }
+
+    #[test]
+    fn mac_fields_rewritten_in_record() {
+        let mut rec = HashMap::from([
+            ("mac_address".to_string(), "0026.9876.1234".to_string()),
+            ("sys_mac".to_string(), "00-1C-73-00-00-01".to_string()),
+            ("interface".to_string(), "0026.9876.1234".to_string()),
+            ("chassis_mac".to_string(), "N/A".to_string()),
+        ]);
+        normalize_mac_fields(&mut rec);
+        assert_eq!(rec["mac_address"], "00:26:98:76:12:34");
+        assert_eq!(rec["sys_mac"], "00:1c:73:00:00:01");
+        assert_eq!(rec["interface"], "0026.9876.1234");
+        assert_eq!(rec["chassis_mac"], "N/A");
+    }
+
+    #[test]
+    fn plain_numbers_coerce() {
+        assert_eq!(coercible_number("6.60"), serde_json::Number::from_f64(6.6));
+        assert_eq!(coercible_number("42"), Some(42.into()));
+        assert_eq!(coercible_number("-14.6"), serde_json::Number::from_f64(-14.6));
+        assert_eq!(coercible_number("18446744073709551615"), Some(u64::MAX.into()));
+    }
+
+    #[test]
+    fn locale_and_unit_values_stay_strings() {
+        for s in [
+            "6.60%", "200Gbps", "1.234,56", "1,234", "1.2.3", ".5", "5.", "-", "", " 42", "0x1F", "1e3",
+            "99999999999999999999",
+        ] {
+            assert_eq!(coercible_number(s), None, "{s:?} must not coerce");
+        }
+    }
+
+    #[test]
+    fn units_split_from_numbers() {
+        assert_eq!(strip_units("6.60%"), Some(("6.60", "%")));
+        assert_eq!(strip_units("200Gbps"), Some(("200", "Gbps")));
+        assert_eq!(strip_units("-2.1 dBm"), Some(("-2.1", "dBm")));
+        assert_eq!(strip_units("200"), None, "no unit to strip");
+        assert_eq!(strip_units("1.234,56"), None);
+        assert_eq!(strip_units("Gbps"), None);
+    }
+
+    #[test]
+    fn interface_abbreviations_expand() {
+        assert_eq!(normalize_interface_name("Gi0/0/1"), "GigabitEthernet0/0/1");
+        assert_eq!(normalize_interface_name("Te1/1"), "TenGigabitEthernet1/1");
+        assert_eq!(normalize_interface_name("Tw1/0/3"), "TwoGigabitEthernet1/0/3");
+        assert_eq!(normalize_interface_name("Twe1/0/1"), "TwentyFiveGigE1/0/1");
+        assert_eq!(normalize_interface_name("Hu0/0/0"), "HundredGigE0/0/0");
+        assert_eq!(normalize_interface_name("Eth1/1"), "Ethernet1/1");
+        assert_eq!(normalize_interface_name("po10"), "Port-channel10");
+        assert_eq!(normalize_interface_name("Gi 0/1"), "GigabitEthernet0/1");
+    }
+
+    #[test]
+    fn interface_full_and_unknown_names_unchanged() {
+        assert_eq!(normalize_interface_name("GigabitEthernet0/0/1"), "GigabitEthernet0/0/1");
+        assert_eq!(normalize_interface_name("HundredGigE0/0/0"), "HundredGigE0/0/0");
+        assert_eq!(normalize_interface_name("ge-0/0/1"), "ge-0/0/1");
+        assert_eq!(normalize_interface_name("mgmt0"), "mgmt0");
+        assert_eq!(normalize_interface_name(" Null0 "), "Null0");
+        assert_eq!(normalize_interface_name("CPU"), "CPU");
+    }
+
+    #[test]
+    fn normalize_adds_temperature_state() {
+        let recs = vec![HashMap::from([
+            ("temperature".to_string(), "66.0".to_string()),
+            ("high_warning".to_string(), "65".to_string()),
+            ("high_critical".to_string(), "70".to_string()),
+        ])];
+        let recs = normalize("show_system_hardware_temperature", recs);
+        assert_eq!(recs[0]["temperature_state"], "warning");
+        assert_eq!(recs[0]["temperature"], "66.0");
+    }
+
+    #[test]
+    fn uptime_spelled_out() {
+        assert_eq!(parse_uptime_seconds("2 weeks, 3 days, 12 hours and 34 minutes"), Some(1_514_040));
+        assert_eq!(parse_uptime_seconds("1 year, 1 day, 1 hour, 1 minute"), Some(31_626_060));
+        assert_eq!(parse_uptime_seconds("45 seconds"), Some(45));
+    }
+
+    #[test]
+    fn uptime_malformed_returns_none() {
+        assert_eq!(parse_uptime_seconds(""), None);
+        assert_eq!(parse_uptime_seconds("3 days, 4"), None);
+        assert_eq!(parse_uptime_seconds("12:34:56"), None);
+        assert_eq!(parse_uptime_seconds("2 fortnights"), None);
+    }
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let cases = [
+            (
+                "show_system_hardware_temperature",
+                vec![row(&[("temperature", "71"), ("high_warning", "65"), ("high_critical", "70")])],
+            ),
+            ("show_environment_temperature", vec![row(&[("sensor", "Inlet"), ("state", "Minor")])]),
+            ("show_version", vec![row(&[("version", "15.2(4)E"), ("uptime", "3 days, 4 hours")])]),
+            (
+                "show_ip_route_summary",
+                vec![
+                    row(&[("route_source", "connected"), ("networks", "2"), ("subnets", "3")]),
+                    row(&[("route_source", "ospf 1"), ("networks", "0"), ("subnets", "10")]),
+                ],
+            ),
+            (
+                "show_route_summary",
+                vec![
+                    row(&[("route_source", "bgp"), ("routes", "900"), ("paths", "1800")]),
+                    row(&[("route_source", "Total"), ("routes", "900"), ("paths", "1800")]),
+                ],
+            ),
+            (
+                "show_interfaces",
+                vec![
+                    row(&[("interface", "Gi0/1"), ("bandwidth", "1000000 Kbit"), ("load_interval", "30 second")]),
+                    row(&[("interface", "Gi0/2"), ("bandwidth", "1000000 Kbit"), ("load_interval", "")]),
+                    row(&[("interface", "ge-0/0/0"), ("mtu", "1514")]),
+                ],
+            ),
+        ];
+        for (key, recs) in cases {
+            let once = normalize(key, recs);
+            assert_eq!(normalize(key, once.clone()), once, "normalize({key}) is not idempotent");
+        }
+    }
+
+    #[test]
+    fn load_interval_default_needs_a_load_interval_field() {
+        let recs = normalize(
+            "show_interfaces",
+            vec![
+                row(&[("interface", "Gi0/2"), ("load_interval", "")]),
+                row(&[("interface", "ge-0/0/0"), ("mtu", "1514")]),
+            ],
+        );
+        assert_eq!(recs[0].get("load_interval_seconds").map(String::as_str), Some("300"));
+        assert_eq!(recs[1].get("load_interval_seconds"), None);
+    }
+
+    #[test]
+    fn normalize_leaves_other_commands_untouched() {
+        let recs = vec![HashMap::from([("temperature".to_string(), "66.0".to_string())])];
+        assert_eq!(normalize("show_version", recs.clone()), recs);
+    }
+}
@@ -1,9 +1,30 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
+#[cfg(feature = "mpsc")]
+use std::sync::mpsc::Sender;
+use std::sync::OnceLock;
 
-use crate::registry;
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
 
+use crate::preprocess::{Preprocessed, Preprocessor};
+use crate::{registry, typed};
+
+type Record = HashMap<String, String>;
+
+/// Items of a record's `List` fields, by field name. The record itself holds
+/// the engine's `[a, b]` rendering, which cannot be split back apart when an
+/// item contains `, `.
+pub(crate) type ListItems = HashMap<String, Vec<String>>;
+
+/// Why a parse request failed.
+///
+/// New variants may be added in minor releases, so downstream code should
+/// branch on [`ParseError::code`] rather than matching variants exhaustively.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ParseError {
     InvalidInput(&'static str),
     TemplateNotFound {
@@ -12,15 +33,41 @@ pub enum ParseError {
     },
     TemplateInvalid(String),
     EngineError(String),
+    /// [`ParseOptions::expect_single`] was set and the parse produced this
+    /// many records.
+    UnexpectedRecordCount(usize),
+    /// A record did not deserialize into the type requested from
+    /// [`parse_records_typed`]. `field` is `None` when serde's error does not
+    /// point at a single field.
+    Deserialize {
+        record: usize,
+        field: Option<String>,
+        message: String,
+    },
 }
 
 impl ParseError {
+    /// Every string [`ParseError::code`] can return. This set is the stable
+    /// compatibility surface: codes are never renamed or removed, and the FFI
+    /// envelope's `error.code` uses the same values.
+    pub const CODES: &'static [&'static str] = &[
+        "INVALID_INPUT",
+        "TEMPLATE_NOT_FOUND",
+        "TEMPLATE_INVALID",
+        "PARSE_ERROR",
+        "UNEXPECTED_RECORD_COUNT",
+        "DESERIALIZE_ERROR",
+    ];
+
+    /// Stable machine-readable error code; one of [`ParseError::CODES`].
     pub fn code(&self) -> &'static str {
         match self {
             Self::InvalidInput(_) => "INVALID_INPUT",
             Self::TemplateNotFound { .. } => "TEMPLATE_NOT_FOUND",
             Self::TemplateInvalid(_) => "TEMPLATE_INVALID",
             Self::EngineError(_) => "PARSE_ERROR",
+            Self::UnexpectedRecordCount(_) => "UNEXPECTED_RECORD_COUNT",
+            Self::Deserialize { .. } => "DESERIALIZE_ERROR",
         }
     }
 }
@@ -35,74 +82,1002 @@ impl fmt::Display for ParseError {
             } => write!(f, "no template for ({platform}, {command_key})"),
             Self::TemplateInvalid(msg) => write!(f, "template compilation failed: {msg}"),
             Self::EngineError(msg) => write!(f, "parse error: {msg}"),
+            Self::UnexpectedRecordCount(n) => write!(f, "expected at most one record, got {n}"),
+            Self::Deserialize {
+                record,
+                field: Some(field),
+                message,
+            } => write!(f, "record {record}, field {field}: {message}"),
+            Self::Deserialize {
+                record,
+                field: None,
+                message,
+            } => write!(f, "record {record}: {message}"),
         }
     }
 }
 
 impl std::error::Error for ParseError {}
 
-pub fn parse_records(
-    platform: &str,
-    command_key: &str,
+/// Options controlling input handling and the shape of the JSON envelope.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Add a `meta` block to success envelopes describing how the request
+    /// was resolved (canonical platform, template path, whether any template
+    /// rule matched, record count, skipped and unmatched lines, whether a
+    /// redundant platform prefix was stripped from the command key).
+    pub include_meta: bool,
+    /// Skip input lines longer than this many bytes before they reach the
+    /// template regexes. Guards against pathological captures (e.g. a
+    /// multi-megabyte line with no newlines). `None` disables the cap.
+    pub max_line_len: Option<usize>,
+    /// Run [`crate::normalize::normalize`] over the records, adding derived
+    /// fields such as `temperature_state`.
+    pub normalize: bool,
+    /// Drop fields whose value is the empty string from each record in the
+    /// JSON envelope. Only `""` counts as empty; `"0"` and `"false"` are kept.
+    pub omit_empty_fields: bool,
+    /// Fail with [`ParseError::UnexpectedRecordCount`] when the parse yields
+    /// more than one record. For `show version`-style commands, where a
+    /// second record means the template misread the output.
+    pub expect_single: bool,
+    /// Add a top-level `schemaVersion` (always [`ENVELOPE_SCHEMA_VERSION`])
+    /// to every JSON envelope, success or error, so consumers can detect
+    /// shape changes.
+    pub include_schema_version: bool,
+    /// When a template `Error` rule aborts the parse, keep what was captured
+    /// up to the offending line instead of failing: every record already
+    /// emitted plus the partially filled one in progress (still subject to
+    /// `Required`). Parsing stops at that line. Lines no rule matches never
+    /// abort a parse, so this only matters for templates with `Error` rules.
+    pub emit_partial_records: bool,
+    /// Sort each JSON record's keys alphabetically instead of in the order
+    /// the template declares its `Value`s. By default, keys the template does
+    /// not declare (e.g. fields added by [`ParseOptions::normalize`]) follow
+    /// the declared ones, sorted. Either way the output is the same from run
+    /// to run.
+    pub sort_record_keys: bool,
+    /// Separator between the items of a `List` field in a CSV cell, see
+    /// [`parse_records_csv_with_options`]. `None` uses
+    /// [`DEFAULT_CSV_LIST_DELIMITER`].
+    pub csv_list_delimiter: Option<String>,
+}
+
+impl ParseOptions {
+    /// Start from the defaults and set only the options you need:
+    /// `ParseOptions::builder().normalize(true).include_meta(true).build()`.
+    pub fn builder() -> ParseOptionsBuilder {
+        ParseOptionsBuilder::default()
+    }
+}
+
+/// Fluent constructor for [`ParseOptions`]; each setter mirrors the field of
+/// the same name.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptionsBuilder {
+    options: ParseOptions,
+}
+
+impl ParseOptionsBuilder {
+    pub fn include_meta(mut self, on: bool) -> Self {
+        self.options.include_meta = on;
+        self
+    }
+
+    pub fn max_line_len(mut self, bytes: usize) -> Self {
+        self.options.max_line_len = Some(bytes);
+        self
+    }
+
+    pub fn normalize(mut self, on: bool) -> Self {
+        self.options.normalize = on;
+        self
+    }
+
+    /// Same as [`ParseOptionsBuilder::normalize`].
+    pub fn normalize_fields(self, on: bool) -> Self {
+        self.normalize(on)
+    }
+
+    pub fn omit_empty_fields(mut self, on: bool) -> Self {
+        self.options.omit_empty_fields = on;
+        self
+    }
+
+    pub fn expect_single(mut self, on: bool) -> Self {
+        self.options.expect_single = on;
+        self
+    }
+
+    pub fn include_schema_version(mut self, on: bool) -> Self {
+        self.options.include_schema_version = on;
+        self
+    }
+
+    pub fn emit_partial_records(mut self, on: bool) -> Self {
+        self.options.emit_partial_records = on;
+        self
+    }
+
+    pub fn sort_record_keys(mut self, on: bool) -> Self {
+        self.options.sort_record_keys = on;
+        self
+    }
+
+    pub fn csv_list_delimiter(mut self, delimiter: &str) -> Self {
+        self.options.csv_list_delimiter = Some(delimiter.to_string());
+        self
+    }
+
+    pub fn build(self) -> ParseOptions {
+        self.options
+    }
+}
+
+/// Default for [`ParseOptions::csv_list_delimiter`].
+pub const DEFAULT_CSV_LIST_DELIMITER: &str = ";";
+
+/// Version of the JSON envelope shape reported by
+/// [`ParseOptions::include_schema_version`]. Bumped whenever a field is
+/// removed, renamed or changes type; additive fields do not bump it.
+pub const ENVELOPE_SCHEMA_VERSION: u32 = 2;
+
+struct Parsed {
+    entry: &'static registry::RegistryEntry,
+    /// Whether a redundant platform prefix was stripped from the command key.
+    key_adjusted: bool,
+    records: Vec<Record>,
+    /// Whether any structural template rule matched the input. Only computed
+    /// when the meta block is requested, since it costs a second pass over the text.
+    matched: bool,
+    /// Lines no rule matched; computed alongside `matched`.
+    unmatched_lines: usize,
+    /// Lines dropped for exceeding [`ParseOptions::max_line_len`].
+    skipped_lines: usize,
+    /// `List` items of each record, serialized as JSON arrays in envelopes.
+    lists: Vec<ListItems>,
+    /// Template fields in declaration order, the default JSON key order (see
+    /// [`ParseOptions::sort_record_keys`]).
+    field_order: Vec<String>,
+}
+
+/// What [`rule_coverage`] found walking a template over the input.
+struct Coverage {
+    /// Whether any structural rule fired.
+    matched_any_rule: bool,
+    /// Lines on which no rule of the current state matched.
+    unmatched_lines: usize,
+    /// Index of the first line that hit an `Error` rule, if any.
+    error_line: Option<usize>,
+}
+
+/// What a matching rule does to the walk in [`rule_coverage`].
+enum Step {
+    /// `Continue`: try the next rule on the same line.
+    Continue,
+    /// `Error`: the engine aborts here.
+    Error,
+    /// Move to another state.
+    Goto(String),
+    /// Move to `End` or `EOF`: no further lines are read.
+    Stop,
+    /// Stay in the current state.
+    Stay,
+}
+
+struct CoverageRule {
+    re: fancy_regex::Regex,
+    /// Whether the rule also matches an empty line (`^\s*$$`, catch-alls).
+    matches_empty: bool,
+    step: Step,
+}
+
+/// A compiled template plus the per-state rules [`rule_coverage`] walks,
+/// built on first use and then kept with the template.
+struct Compiled {
+    template: textfsm_core::Template,
+    /// Record field names in declaration order, the order of the engine's rows.
+    fields: Vec<String>,
+    coverage_rules: OnceLock<HashMap<String, Vec<CoverageRule>>>,
+}
+
+impl Compiled {
+    fn coverage_rules(&self) -> &HashMap<String, Vec<CoverageRule>> {
+        self.coverage_rules.get_or_init(|| {
+            let template = &self.template;
+            template
+                .state_order()
+                .iter()
+                .filter_map(|name| template.get_state(name))
+                .map(|state| {
+                    let rules = state
+                        .rules
+                        .iter()
+                        .filter_map(|r| {
+                            let re = fancy_regex::Regex::new(&r.regex_pattern).ok()?;
+                            let step = match (&r.line_op, &r.transition) {
+                                (textfsm_core::LineOp::Continue, _) => Step::Continue,
+                                (textfsm_core::LineOp::Error, _) => Step::Error,
+                                (_, textfsm_core::Transition::State(next)) => Step::Goto(next.clone()),
+                                (_, textfsm_core::Transition::End | textfsm_core::Transition::Eof) => Step::Stop,
+                                (_, textfsm_core::Transition::Stay) => Step::Stay,
+                            };
+                            let matches_empty = matches!(re.is_match(""), Ok(true));
+                            Some(CoverageRule {
+                                re,
+                                matches_empty,
+                                step,
+                            })
+                        })
+                        .collect();
+                    (state.name.clone(), rules)
+                })
+                .collect()
+        })
+    }
+}
+
+/// Walk the template state machine over `text`, noting whether any rule
+/// fired and how many lines nothing matched. Rules that also match an empty
+/// line are not evidence of recognised structure, but do count as matching
+/// their line. The walk stops where the engine would (an `Error` action or a
+/// move to `End`/`EOF`).
+fn rule_coverage(compiled: &Compiled, text: &str) -> Coverage {
+    let states = compiled.coverage_rules();
+    let mut coverage = Coverage {
+        matched_any_rule: false,
+        unmatched_lines: 0,
+        error_line: None,
+    };
+    let mut state = "Start";
+    'lines: for (n, line) in text.lines().enumerate() {
+        let Some(rules) = states.get(state) else {
+            break;
+        };
+        let mut hit = false;
+        for rule in rules {
+            if !matches!(rule.re.is_match(line), Ok(true)) {
+                continue;
+            }
+            hit = true;
+            if !rule.matches_empty {
+                coverage.matched_any_rule = true;
+            }
+            match &rule.step {
+                Step::Continue => continue,
+                Step::Error => {
+                    coverage.error_line = Some(n);
+                    break 'lines;
+                }
+                Step::Goto(next) => state = next.as_str(),
+                Step::Stop => break 'lines,
+                Step::Stay => {}
+            }
+            break;
+        }
+        if !hit {
+            coverage.unmatched_lines += 1;
+        }
+    }
+    coverage
+}
+
+/// Record field names (lowercased) in the order the template declares them.
+fn field_order(template_text: &str) -> Vec<String> {
+    template_text
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.strip_prefix("Value ")?.split_whitespace();
+            let first = words.next()?;
+            let name = match words.next() {
+                Some(next) if !next.starts_with('(') => next,
+                _ => first,
+            };
+            Some(name.to_ascii_lowercase())
+        })
+        .collect()
+}
+
+/// Split a `[a, b]` rendering back into its items, for records that come
+/// without their [`ListItems`]. Lossy when an item contains `, `. A value not
+/// in that form is a single item.
+pub(crate) fn list_items(value: &str) -> Vec<&str> {
+    match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        Some("") => Vec::new(),
+        Some(inner) => inner.split(", ").collect(),
+        None if value.is_empty() => Vec::new(),
+        None => vec![value],
+    }
+}
+
+fn compile_template(template_text: &str) -> Result<Compiled, ParseError> {
+    let template =
+        textfsm_core::Template::parse_str(template_text).map_err(|e| ParseError::TemplateInvalid(e.to_string()))?;
+    Ok(Compiled {
+        template,
+        fields: field_order(template_text),
+        coverage_rules: OnceLock::new(),
+    })
+}
+
+/// Run the engine over `text`, keeping each `List` value's items next to the
+/// record that renders them.
+fn engine_rows(compiled: &Compiled, text: &str) -> Result<(Vec<Record>, Vec<ListItems>), ParseError> {
+    let rows = compiled
+        .template
+        .parser()
+        .parse_text(text)
+        .map_err(|e| ParseError::EngineError(e.to_string()))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let mut record = Record::with_capacity(row.len());
+            let mut lists = ListItems::new();
+            for (name, value) in compiled.fields.iter().zip(row) {
+                let rendered = match value {
+                    textfsm_core::Value::List(items) => {
+                        let rendered = format!("[{}]", items.join(", "));
+                        lists.insert(name.clone(), items);
+                        rendered
+                    }
+                    other => other.to_string(),
+                };
+                record.insert(name.clone(), rendered);
+            }
+            (record, lists)
+        })
+        .unzip())
+}
+
+/// One engine pass over a compiled template: preprocess, then run.
+/// Keeps the cleaned text so callers can inspect it afterwards.
+struct Engine<'a> {
+    text: Cow<'a, str>,
+    records: Vec<Record>,
+    lists: Vec<ListItems>,
+    skipped_lines: usize,
+}
+
+fn run_engine<'a>(
+    compiled: &Compiled,
+    output_text: &'a str,
+    options: &ParseOptions,
+) -> Result<Engine<'a>, ParseError> {
+    let Preprocessed {
+        text,
+        skipped_lines,
+    } = Preprocessor::from_options(options).run(output_text);
+
+    let (records, lists) = match engine_rows(compiled, &text) {
+        Ok(rows) => rows,
+        Err(e) => {
+            let error_line = options
+                .emit_partial_records
+                .then(|| rule_coverage(compiled, &text).error_line)
+                .flatten();
+            let Some(n) = error_line else {
+                return Err(e);
+            };
+            // Re-run on the lines before the one that hit `Error`; end of
+            // input then records the block that was in progress.
+            let head: String = text.split_inclusive('\n').take(n).collect();
+            engine_rows(compiled, &head)?
+        }
+    };
+
+    Ok(Engine {
+        text,
+        records,
+        lists,
+        skipped_lines,
+    })
+}
+
+/// Run `template_text` over `output_text` directly, with no registry lookup.
+///
+/// Honours [`ParseOptions::max_line_len`] and [`ParseOptions::expect_single`].
+/// [`ParseOptions::normalize`] is ignored: derived fields depend on the
+/// command key, which this entry point does not have.
+pub fn run_template(
+    template_text: &str,
     output_text: &str,
+    options: &ParseOptions,
 ) -> Result<Vec<HashMap<String, String>>, ParseError> {
+    run_template_engine(template_text, output_text, options).map(|(_, engine)| engine.records)
+}
+
+fn run_template_engine<'a>(
+    template_text: &str,
+    output_text: &'a str,
+    options: &ParseOptions,
+) -> Result<(Compiled, Engine<'a>), ParseError> {
+    if template_text.is_empty() {
+        return Err(ParseError::InvalidInput("template_text"));
+    }
+    if output_text.is_empty() {
+        return Err(ParseError::InvalidInput("output_text"));
+    }
+
+    let template = compile_template(template_text)?;
+    let engine = run_engine(&template, output_text, options)?;
+    if options.expect_single && engine.records.len() > 1 {
+        return Err(ParseError::UnexpectedRecordCount(engine.records.len()));
+    }
+    Ok((template, engine))
+}
+
+/// [`run_template`] as `{"ok":true,"records":[...]}` or the usual error
+/// envelope. Records are shaped as in [`parse_json`], `List` fields included;
+/// there is no `platform`/`commandKey`, since no registry entry is involved.
+pub fn run_template_json(template_text: &str, output_text: &str, options: &ParseOptions) -> String {
+    let result = run_template_engine(template_text, output_text, options);
+    let (head, records) = match &result {
+        Ok((template, engine)) => {
+            let records = engine
+                .records
+                .iter()
+                .zip(&engine.lists)
+                .map(|(rec, lists)| record_json(rec, lists, &template.fields, options))
+                .collect();
+            (serde_json::json!({ "ok": true, "records": null }), records)
+        }
+        Err(e) => {
+            let head = serde_json::json!({
+                "ok": false,
+                "error": {
+                    "code": e.code(),
+                    "message": e.to_string(),
+                }
+            });
+            (head, Vec::new())
+        }
+    };
+    serde_json::to_string(&Envelope { head, records }).unwrap_or_default()
+}
+
+/// A template's declarations, as reported by [`validate_template`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateInfo {
+    /// Declared values, in template order.
+    pub values: Vec<TemplateValue>,
+    /// State names, in template order.
+    pub states: Vec<String>,
+}
+
+/// One `Value` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateValue {
+    /// Name as declared (record fields are its lowercase form).
+    pub name: String,
+    /// Options such as `Filldown` or `List`; empty when none are given.
+    pub modifiers: Vec<String>,
+}
+
+/// Compile `template_text` without running it and report what it declares.
+///
+/// Each `Value` regex is checked on its own first, so a bad one comes back
+/// as [`ParseError::TemplateInvalid`] naming its line.
+pub fn validate_template(template_text: &str) -> Result<TemplateInfo, ParseError> {
+    if template_text.is_empty() {
+        return Err(ParseError::InvalidInput("template_text"));
+    }
+
+    let mut values = Vec::new();
+    for (n, line) in template_text.lines().enumerate() {
+        let Some(rest) = line.strip_prefix("Value ") else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let (first, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let tail = tail.trim_start();
+        let (modifiers, name, regex) = if tail.starts_with('(') || tail.is_empty() {
+            (Vec::new(), first, tail)
+        } else {
+            let (name, regex) = tail.split_once(char::is_whitespace).unwrap_or((tail, ""));
+            (first.split(',').map(str::to_string).collect(), name, regex.trim_start())
+        };
+        if let Err(e) = fancy_regex::Regex::new(regex) {
+            return Err(ParseError::TemplateInvalid(format!("line {}: `{line}`: {e}", n + 1)));
+        }
+        values.push(TemplateValue {
+            name: name.to_string(),
+            modifiers,
+        });
+    }
+
+    let compiled = compile_template(template_text)?;
+    Ok(TemplateInfo {
+        values,
+        states: compiled.template.state_order().iter().map(|s| s.to_string()).collect(),
+    })
+}
+
+/// A registry template resolved and compiled once, so many outputs of the
+/// same command can be parsed without repeating the lookup. See [`prepare`].
+pub struct PreparedTemplate {
+    /// Platform as passed to [`prepare`], echoed in JSON envelopes.
+    platform: String,
+    entry: &'static registry::RegistryEntry,
+    key_adjusted: bool,
+    template: Compiled,
+}
+
+/// Resolve and compile the template for `platform` / `command_key`.
+pub fn prepare(platform: &str, command_key: &str) -> Result<PreparedTemplate, ParseError> {
     if platform.is_empty() {
         return Err(ParseError::InvalidInput("platform"));
     }
     if command_key.is_empty() {
         return Err(ParseError::InvalidInput("command_key"));
     }
-    if output_text.is_empty() {
+
+    let not_found = || ParseError::TemplateNotFound {
+        platform: platform.into(),
+        command_key: command_key.into(),
+    };
+    let (entry, key_adjusted) = registry::lookup_lenient(platform, command_key).ok_or_else(not_found)?;
+    let template_text = registry::load_template_text(entry).ok_or_else(not_found)?;
+
+    Ok(PreparedTemplate {
+        platform: platform.into(),
+        entry,
+        key_adjusted,
+        template: compile_template(template_text)?,
+    })
+}
+
+impl PreparedTemplate {
+    fn parse(&self, output_text: &str, options: &ParseOptions) -> Result<Parsed, ParseError> {
+        if output_text.is_empty() {
+            return Err(ParseError::InvalidInput("output_text"));
+        }
+
+        let Engine {
+            text,
+            records,
+            mut lists,
+            skipped_lines,
+        } = run_engine(&self.template, output_text, options)?;
+
+        let (records, lists) = match &self.entry.merge_on {
+            Some(on) => {
+                let (records, kept) = crate::records::merge_records_indexed(records, on);
+                let lists = kept.into_iter().map(|i| std::mem::take(&mut lists[i])).collect();
+                (records, lists)
+            }
+            None => (records, lists),
+        };
+
+        if options.expect_single && records.len() > 1 {
+            return Err(ParseError::UnexpectedRecordCount(records.len()));
+        }
+
+        let records = if options.normalize {
+            crate::normalize::normalize(&self.entry.command_key, records)
+        } else {
+            records
+        };
+
+        let (matched, unmatched_lines) = if options.include_meta {
+            let coverage = rule_coverage(&self.template, &text);
+            (!records.is_empty() || coverage.matched_any_rule, coverage.unmatched_lines)
+        } else {
+            (false, 0)
+        };
+
+        Ok(Parsed {
+            entry: self.entry,
+            key_adjusted: self.key_adjusted,
+            records,
+            matched,
+            unmatched_lines,
+            skipped_lines,
+            lists,
+            field_order: self.template.fields.clone(),
+        })
+    }
+
+    /// Record field names (lowercased) in the order the template declares
+    /// them, the column order of [`parse_records_csv`].
+    pub fn fields(&self) -> &[String] {
+        &self.template.fields
+    }
+
+    /// [`parse_records_with_options`] against the prepared template.
+    pub fn parse_records(
+        &self,
+        output_text: &str,
+        options: &ParseOptions,
+    ) -> Result<Vec<HashMap<String, String>>, ParseError> {
+        self.parse(output_text, options).map(|parsed| parsed.records)
+    }
+
+    /// [`parse_json_with_options`] against the prepared template.
+    pub fn parse_json(&self, output_text: &str, options: &ParseOptions) -> String {
+        envelope_json(&self.platform, self.parse(output_text, options), options)
+    }
+}
+
+fn resolve_and_parse(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> Result<Parsed, ParseError> {
+    // Input errors are reported before lookup errors, argument by argument.
+    if !platform.is_empty() && !command_key.is_empty() && output_text.is_empty() {
         return Err(ParseError::InvalidInput("output_text"));
     }
+    prepare(platform, command_key)?.parse(output_text, options)
+}
 
-    let entry = registry::lookup(platform, command_key).ok_or_else(|| {
-        ParseError::TemplateNotFound {
-            platform: platform.into(),
-            command_key: command_key.into(),
-        }
-    })?;
+pub fn parse_records(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+) -> Result<Vec<HashMap<String, String>>, ParseError> {
+    resolve_and_parse(platform, command_key, output_text, &ParseOptions::default()).map(|parsed| parsed.records)
+}
+
+/// [`parse_records`] with explicit [`ParseOptions`].
+pub fn parse_records_with_options(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> Result<Vec<HashMap<String, String>>, ParseError> {
+    resolve_and_parse(platform, command_key, output_text, options).map(|parsed| parsed.records)
+}
+
+/// Same as [`parse_records_with_options`].
+pub fn parse_records_opts(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> Result<Vec<HashMap<String, String>>, ParseError> {
+    parse_records_with_options(platform, command_key, output_text, options)
+}
+
+/// [`parse_records`], deserializing each record into `T`.
+///
+/// Field values are parsed to whatever `T` asks for: integers, floats (in the
+/// locale-free [`crate::normalize::coercible_number`] grammar) and `bool`s
+/// (`true`/`false`) from their text, `Option` fields as `None` when empty,
+/// `Vec` fields from `List` values, unit enum variants by name and strings
+/// verbatim. Record fields `T` does not declare are ignored unless it
+/// uses `#[serde(deny_unknown_fields)]`.
+pub fn parse_records_typed<T: DeserializeOwned>(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+) -> Result<Vec<T>, ParseError> {
+    let parsed = resolve_and_parse(platform, command_key, output_text, &ParseOptions::default())?;
+    parsed
+        .records
+        .iter()
+        .zip(&parsed.lists)
+        .enumerate()
+        .map(|(record, (rec, lists))| {
+            typed::from_record(rec, lists).map_err(|e| ParseError::Deserialize {
+                record,
+                field: e.field,
+                message: e.message,
+            })
+        })
+        .collect()
+}
+
+/// Records as newline-delimited JSON: one compact object per line, each
+/// terminated by `\n`, with no envelope. Objects have the same shape as the
+/// envelope's `records` (template key order, `List` fields as arrays). Zero records
+/// give an empty string.
+pub fn parse_records_ndjson(platform: &str, command_key: &str, output_text: &str) -> Result<String, ParseError> {
+    let options = ParseOptions::default();
+    let parsed = resolve_and_parse(platform, command_key, output_text, &options)?;
+    let mut out = String::new();
+    for (rec, lists) in parsed.records.iter().zip(&parsed.lists) {
+        let line = serde_json::to_string(&record_json(rec, lists, &parsed.field_order, &options))
+            .map_err(|e| ParseError::EngineError(e.to_string()))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Records as CSV: a header row of the template's fields (lowercased, in
+/// declaration order), then one row per record. Fields the template does not
+/// declare, such as those added by [`ParseOptions::normalize`], get columns
+/// after the declared ones, sorted. Cells are quoted per RFC 4180 and rows
+/// end in `\r\n`. Zero records still give the header row.
+pub fn parse_records_csv(platform: &str, command_key: &str, output_text: &str) -> Result<String, ParseError> {
+    parse_records_csv_with_options(platform, command_key, output_text, &ParseOptions::default())
+}
+
+/// [`parse_records_csv`] with options. `List` fields are joined with
+/// [`ParseOptions::csv_list_delimiter`].
+pub fn parse_records_csv_with_options(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> Result<String, ParseError> {
+    let parsed = resolve_and_parse(platform, command_key, output_text, options)?;
+    let delimiter = options.csv_list_delimiter.as_deref().unwrap_or(DEFAULT_CSV_LIST_DELIMITER);
+
+    let mut columns: Vec<&str> = parsed.field_order.iter().map(String::as_str).collect();
+    let extra: BTreeSet<&str> = parsed
+        .records
+        .iter()
+        .flat_map(|rec| rec.keys().map(String::as_str))
+        .filter(|k| !parsed.field_order.iter().any(|f| f == k))
+        .collect();
+    columns.extend(extra);
 
-    let template_text = registry::load_template_text(entry).ok_or_else(|| {
-        ParseError::TemplateNotFound {
-            platform: platform.into(),
-            command_key: command_key.into(),
+    let mut out = String::new();
+    push_csv_row(&mut out, columns.iter().map(|c| Cow::Borrowed(*c)));
+    for (rec, lists) in parsed.records.iter().zip(&parsed.lists) {
+        push_csv_row(
+            &mut out,
+            columns.iter().map(|c| match (lists.get(*c), rec.get(*c)) {
+                (Some(items), _) => Cow::Owned(items.join(delimiter)),
+                (None, Some(v)) => Cow::Borrowed(v.as_str()),
+                (None, None) => Cow::Borrowed(""),
+            }),
+        );
+    }
+    Ok(out)
+}
+
+fn push_csv_row<'a>(out: &mut String, cells: impl Iterator<Item = Cow<'a, str>>) {
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if cell.contains([',', '"', '\r', '\n']) {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&cell);
         }
-    })?;
+    }
+    out.push_str("\r\n");
+}
 
-    let template = textfsm_core::Template::parse_str(template_text)
-        .map_err(|e| ParseError::TemplateInvalid(e.to_string()))?;
+/// Typed counterpart of the JSON `meta` block, from [`parse_records_meta`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMeta {
+    /// Number of records returned.
+    pub record_count: usize,
+    /// Input lines no template rule matched.
+    pub unmatched_lines: usize,
+    /// Whether any template rule recognised the input. `false` with no
+    /// records means the output was not what the template expects, rather
+    /// than a command that legitimately printed nothing to parse.
+    pub matched_any_rule: bool,
+}
+
+/// [`parse_records`] plus a [`ParseMeta`] telling an empty result that
+/// matched nothing apart from one with nothing to report.
+pub fn parse_records_meta(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+) -> Result<(Vec<HashMap<String, String>>, ParseMeta), ParseError> {
+    let options = ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let parsed = resolve_and_parse(platform, command_key, output_text, &options)?;
+    let meta = ParseMeta {
+        record_count: parsed.records.len(),
+        unmatched_lines: parsed.unmatched_lines,
+        matched_any_rule: parsed.matched,
+    };
+    Ok((parsed.records, meta))
+}
+
+/// Parse a single-record command and return that record, or `None` when the
+/// output produced no records. Extra records are ignored; use
+/// [`parse_single_with_options`] with [`ParseOptions::expect_single`] to
+/// reject them instead.
+pub fn parse_single(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+) -> Result<Option<HashMap<String, String>>, ParseError> {
+    parse_single_with_options(platform, command_key, output_text, &ParseOptions::default())
+}
 
-    let mut parser = template.parser();
+pub fn parse_single_with_options(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> Result<Option<HashMap<String, String>>, ParseError> {
+    resolve_and_parse(platform, command_key, output_text, options).map(|parsed| parsed.records.into_iter().next())
+}
 
-    parser
-        .parse_text_to_dicts(output_text)
-        .map_err(|e| ParseError::EngineError(e.to_string()))
+/// Like [`parse_records`], but passes every record through `f` first.
+///
+/// `f` may mutate the record in place; returning `false` drops it.
+pub fn parse_records_with<F>(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    mut f: F,
+) -> Result<Vec<HashMap<String, String>>, ParseError>
+where
+    F: FnMut(&mut HashMap<String, String>) -> bool,
+{
+    let mut records = parse_records(platform, command_key, output_text)?;
+    records.retain_mut(|rec| f(rec));
+    Ok(records)
 }
 
 pub fn parse_json(platform: &str, command_key: &str, output_text: &str) -> String {
-    match parse_records(platform, command_key, output_text) {
-        Ok(records) => {
-            let records_json =
-                serde_json::to_value(&records).unwrap_or(serde_json::Value::Array(vec![]));
+    parse_json_with_options(platform, command_key, output_text, &ParseOptions::default())
+}
+
+/// Parse, then send each record to `tx` in order.
+///
+/// The engine parses the whole output before the first record is sent, so
+/// this hands records to a consumer thread but does not overlap parsing with
+/// consuming. Returns once every record has been sent, or early (still `Ok`)
+/// if the receiver hangs up. Nothing is sent when the parse fails.
+#[cfg(feature = "mpsc")]
+pub fn parse_to_channel(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    tx: Sender<HashMap<String, String>>,
+) -> Result<(), ParseError> {
+    parse_to_channel_with_options(platform, command_key, output_text, &ParseOptions::default(), tx)
+}
 
-            serde_json::json!({
+/// [`parse_to_channel`] with options.
+#[cfg(feature = "mpsc")]
+pub fn parse_to_channel_with_options(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+    tx: Sender<HashMap<String, String>>,
+) -> Result<(), ParseError> {
+    let records = parse_records_with_options(platform, command_key, output_text, options)?;
+    for rec in records {
+        if tx.send(rec).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_json_with_options(
+    platform: &str,
+    command_key: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> String {
+    envelope_json(platform, resolve_and_parse(platform, command_key, output_text, options), options)
+}
+
+/// A record's JSON fields, serialized as an object in exactly this order.
+/// Kept out of `serde_json::Value`, whose maps re-sort their keys.
+struct JsonRecord<'a>(Vec<(&'a str, serde_json::Value)>);
+
+impl Serialize for JsonRecord<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+/// One record as a JSON object: `List` fields as arrays, everything else as
+/// strings. Keys are in template order, or sorted with
+/// [`ParseOptions::sort_record_keys`]; never HashMap order, which differs
+/// from run to run.
+fn record_json<'a>(
+    rec: &'a Record,
+    lists: &ListItems,
+    field_order: &[String],
+    options: &ParseOptions,
+) -> JsonRecord<'a> {
+    let sorted: BTreeMap<&'a str, &'a str> = rec
+        .iter()
+        .filter(|(_, v)| !(options.omit_empty_fields && v.is_empty()))
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    let mut fields: Vec<(&'a str, &'a str)> = Vec::with_capacity(sorted.len());
+    if options.sort_record_keys {
+        fields.extend(sorted);
+    } else {
+        let declared: HashSet<&str> = field_order.iter().map(String::as_str).collect();
+        fields.extend(field_order.iter().filter_map(|k| sorted.get_key_value(k.as_str())).map(|(k, v)| (*k, *v)));
+        fields.extend(sorted.iter().filter(|(k, _)| !declared.contains(*k)).map(|(k, v)| (*k, *v)));
+    }
+    let fields = fields
+        .into_iter()
+        .map(|(k, v)| {
+            let value = if let Some(items) = lists.get(k) {
+                items.as_slice().into()
+            } else {
+                v.into()
+            };
+            (k, value)
+        })
+        .collect();
+    JsonRecord(fields)
+}
+
+/// An envelope whose `records` placeholder is written from [`JsonRecord`]s,
+/// keeping their key order. Everything else serializes as in `head`.
+struct Envelope<'a> {
+    head: serde_json::Value,
+    records: Vec<JsonRecord<'a>>,
+}
+
+impl Serialize for Envelope<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(head) = self.head.as_object() else {
+            return self.head.serialize(serializer);
+        };
+        let mut map = serializer.serialize_map(Some(head.len()))?;
+        for (k, v) in head {
+            if k == "records" {
+                map.serialize_entry(k, &self.records)?;
+            } else {
+                map.serialize_entry(k, v)?;
+            }
+        }
+        map.end()
+    }
+}
+
+fn envelope_json(platform: &str, result: Result<Parsed, ParseError>, options: &ParseOptions) -> String {
+    let (mut envelope, records) = match &result {
+        Ok(parsed) => {
+            let records = parsed
+                .records
+                .iter()
+                .zip(&parsed.lists)
+                .map(|(rec, lists)| record_json(rec, lists, &parsed.field_order, options))
+                .collect();
+
+            let mut envelope = serde_json::json!({
                 "ok": true,
                 "platform": platform,
-                "commandKey": command_key,
-                "records": records_json,
-            })
-            .to_string()
-        }
-        Err(e) => serde_json::json!({
-            "ok": false,
-            "error": {
-                "code": e.code(),
-                "message": e.to_string(),
+                "commandKey": parsed.entry.command_key,
+                "records": null,
+            });
+            if options.include_meta {
+                envelope["meta"] = serde_json::json!({
+                    "resolvedPlatform": parsed.entry.platform,
+                    "templatePath": parsed.entry.template,
+                    "matched": parsed.matched,
+                    "recordCount": parsed.records.len(),
+                    "keyAdjusted": parsed.key_adjusted,
+                    "skippedLines": parsed.skipped_lines,
+                    "unmatchedLines": parsed.unmatched_lines,
+                });
             }
-        })
-        .to_string(),
+            (envelope, records)
+        }
+        Err(e) => {
+            let envelope = serde_json::json!({
+                "ok": false,
+                "error": {
+                    "code": e.code(),
+                    "message": e.to_string(),
+                }
+            });
+            (envelope, Vec::new())
+        }
+    };
+    if options.include_schema_version {
+        envelope["schemaVersion"] = ENVELOPE_SCHEMA_VERSION.into();
     }
+    serde_json::to_string(&Envelope { head: envelope, records }).unwrap_or_default()
 }
 
 pub fn parse_command_records(
@@ -118,3 +1093,164 @@ pub fn parse_command_json(platform: &str, command: &str, output_text: &str) -> S
     let (key, _) = registry::lookup_command(platform, command);
     parse_json(platform, &key, output_text)
 }
+
+pub fn parse_command_json_with_options(
+    platform: &str,
+    command: &str,
+    output_text: &str,
+    options: &ParseOptions,
+) -> String {
+    let (key, _) = registry::lookup_command(platform, command);
+    parse_json_with_options(platform, &key, output_text, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IFACES: &str = "\
+Value Required NAME (\\S+)
+Value Filldown SLOT (\\d+)
+Value List ADDR (\\S+)
+
+Start
+  ^slot ${SLOT}
+  ^iface \\S+ -> Continue.Record
+  ^iface ${NAME}
+  ^\\s+addr ${ADDR}
+";
+
+    #[test]
+    fn run_template_applies_value_options() {
+        let text = "  addr 9.9.9.9\nslot 1\niface eth0\n  addr 10.0.0.1\n  addr 10.0.0.2\niface eth1\n";
+        let recs = run_template(IFACES, text, &ParseOptions::default()).unwrap();
+
+        assert_eq!(recs.len(), 2, "Required NAME drops the record before eth0");
+        assert_eq!(recs[0]["name"], "eth0");
+        assert_eq!(recs[0]["slot"], "1");
+        assert_eq!(recs[0]["addr"], "[10.0.0.1, 10.0.0.2]");
+        assert_eq!(recs[1]["name"], "eth1");
+        assert_eq!(recs[1]["slot"], "1", "Filldown carries SLOT forward");
+        assert_eq!(recs[1]["addr"], "[]");
+    }
+
+    #[test]
+    fn emit_partial_records_keeps_block_cut_short_by_error() {
+        let template = "\
+Value Required NAME (\\S+)
+Value MTU (\\d+)
+Value SPEED (\\S+)
+
+Start
+  ^iface \\S+ -> Continue.Record
+  ^iface ${NAME}
+  ^\\s+mtu ${MTU}
+  ^\\s+speed ${SPEED}
+  ^. -> Error
+";
+        let text = "iface eth0\n  mtu 1500\n  speed 10G\niface eth1\n  mtu 9000\n  %garbled line\niface eth2\n";
+
+        let err = run_template(template, text, &ParseOptions::default()).unwrap_err();
+        assert_eq!(err.code(), "PARSE_ERROR");
+
+        let options = ParseOptions::builder().emit_partial_records(true).build();
+        let recs = run_template(template, text, &options).unwrap();
+        assert_eq!(recs.len(), 2, "parsing stops at the garbled line, so eth2 is not reached");
+        assert_eq!(recs[0]["name"], "eth0");
+        assert_eq!(recs[1]["name"], "eth1");
+        assert_eq!(recs[1]["mtu"], "9000");
+        assert_eq!(recs[1]["speed"], "", "fields after the error stay empty");
+    }
+
+    #[test]
+    fn run_template_json_renders_list_fields_as_arrays() {
+        let text = "iface eth0\n  addr 10.0.0.1\n  addr 10.0.0.2\n";
+        let v: serde_json::Value =
+            serde_json::from_str(&run_template_json(IFACES, text, &ParseOptions::default())).unwrap();
+        assert_eq!(v["ok"], true, "{v}");
+        assert_eq!(v["records"][0]["addr"], serde_json::json!(["10.0.0.1", "10.0.0.2"]));
+        assert!(v.get("platform").is_none());
+
+        let v: serde_json::Value =
+            serde_json::from_str(&run_template_json("", text, &ParseOptions::default())).unwrap();
+        assert_eq!(v["error"]["code"], "INVALID_INPUT");
+    }
+
+    #[test]
+    fn list_items_split_engine_rendering() {
+        assert_eq!(list_items("[10.0.0.1, 10.0.0.2]"), ["10.0.0.1", "10.0.0.2"]);
+        assert!(list_items("[]").is_empty());
+        assert!(list_items("").is_empty());
+        assert_eq!(list_items("Gi0/1"), ["Gi0/1"]);
+    }
+
+    #[test]
+    fn run_template_honours_input_options() {
+        let text = format!("iface eth0\n  addr {}\niface eth1\n", "x".repeat(64));
+        let options = ParseOptions {
+            max_line_len: Some(32),
+            ..Default::default()
+        };
+        let recs = run_template(IFACES, &text, &options).unwrap();
+        assert_eq!(recs[0]["addr"], "[]", "over-long line never reaches the template");
+
+        let options = ParseOptions {
+            expect_single: true,
+            ..Default::default()
+        };
+        let err = run_template(IFACES, &text, &options).unwrap_err();
+        assert_eq!(err.code(), "UNEXPECTED_RECORD_COUNT");
+    }
+
+    #[test]
+    fn run_template_reports_bad_input() {
+        let opts = ParseOptions::default();
+        assert_eq!(run_template("", "x\n", &opts).unwrap_err().code(), "INVALID_INPUT");
+        assert_eq!(run_template(IFACES, "", &opts).unwrap_err().code(), "INVALID_INPUT");
+        assert_eq!(
+            run_template("Value X (\\d+\n\nStart\n  ^${X}\n", "1\n", &opts).unwrap_err().code(),
+            "TEMPLATE_INVALID"
+        );
+    }
+
+    #[test]
+    fn validate_template_reports_declarations() {
+        let info = validate_template(
+            "Value Filldown,Required VRF (\\S+)\nValue List MEMBERS (\\S+)\nValue NAME (\\S+)\n\n\
+             Start\n  ^VRF ${VRF} -> Members\n\nMembers\n  ^  ${MEMBERS}\n  ^${NAME} -> Record\n",
+        )
+        .unwrap();
+        let values: Vec<(&str, Vec<&str>)> = info
+            .values
+            .iter()
+            .map(|v| (v.name.as_str(), v.modifiers.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            values,
+            [("VRF", vec!["Filldown", "Required"]), ("MEMBERS", vec!["List"]), ("NAME", vec![])]
+        );
+        assert_eq!(info.states, ["Start", "Members"]);
+
+        let err = validate_template("Value X (\\d+)\nValue Y ([a-z)\n\nStart\n  ^${X}\n").unwrap_err();
+        assert_eq!(err.code(), "TEMPLATE_INVALID");
+        assert!(err.to_string().contains("line 2: `Value Y ([a-z)`"), "{err}");
+        assert_eq!(validate_template("").unwrap_err().code(), "INVALID_INPUT");
+    }
+
+    #[test]
+    fn csv_cells_are_quoted_per_rfc_4180() {
+        let mut out = String::new();
+        let cells = ["plain", "a,b", "say \"hi\"", "two\nlines", ""];
+        push_csv_row(&mut out, cells.into_iter().map(Cow::Borrowed));
+        assert_eq!(out, "plain,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",\r\n");
+    }
+
+    #[test]
+    fn continue_with_state_change_is_rejected_at_compile_time() {
+        // TextFSM forbids `Continue` with a new state, so a template cannot
+        // hand a line back and forth between states.
+        let looping = "Value X (\\w+)\n\nStart\n  ^${X} -> Continue Loop\n\nLoop\n  ^${X} -> Continue Start\n";
+        let err = run_template(looping, "a\n", &ParseOptions::default()).unwrap_err();
+        assert_eq!(err.code(), "TEMPLATE_INVALID", "{err}");
+    }
+}
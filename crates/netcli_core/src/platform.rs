@@ -1 +1,292 @@
-// Platform taxonomy — will be rebuilt with all ~55 NTC-Templates platforms.
+// Platform taxonomy — canonical platform slugs and per-vendor CLI traits.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::registry;
+
+/// A platform the core does not ship, plugged in at runtime with
+/// [`register_platform`]. Built-in platforms always take precedence.
+pub trait PlatformProvider: Send + Sync {
+    /// Canonical slug, e.g. `acme_os`.
+    fn slug(&self) -> &str;
+    /// Other names that resolve to [`PlatformProvider::slug`].
+    fn aliases(&self) -> &[&str];
+    /// Regex matching a prompt line, as returned by
+    /// [`Platform::default_prompt_regex`].
+    fn prompt_regex(&self) -> &str;
+    /// Built-in platform whose templates parse this one's output, e.g.
+    /// `cisco_ios` for an IOS-derived OS. `None` means nothing parses.
+    fn templates_from(&self) -> Option<&str> {
+        None
+    }
+}
+
+static PROVIDERS: RwLock<Vec<&'static dyn PlatformProvider>> = RwLock::new(Vec::new());
+
+/// Make `provider` resolvable by its slug and aliases everywhere a platform
+/// is accepted. Returns `false`, registering nothing, if any of those names
+/// already resolves to a built-in or previously registered platform.
+pub fn register_platform(provider: Box<dyn PlatformProvider>) -> bool {
+    let mut providers = PROVIDERS.write().unwrap_or_else(|e| e.into_inner());
+    let taken = |name: &str| {
+        registry::builtin_platform(name).is_some() || providers.iter().any(|p| provides(*p, name))
+    };
+    if taken(provider.slug()) || provider.aliases().iter().any(|a| taken(a)) {
+        return false;
+    }
+    providers.push(Box::leak(provider));
+    true
+}
+
+fn provides(provider: &dyn PlatformProvider, name: &str) -> bool {
+    provider.slug() == name || provider.aliases().contains(&name)
+}
+
+/// The registered provider whose slug or alias is `name`.
+pub(crate) fn provider(name: &str) -> Option<&'static dyn PlatformProvider> {
+    let providers = PROVIDERS.read().unwrap_or_else(|e| e.into_inner());
+    providers.iter().copied().find(|p| provides(*p, name))
+}
+
+fn runtime_aliases() -> &'static RwLock<HashMap<String, &'static str>> {
+    static ALIASES: OnceLock<RwLock<HashMap<String, &'static str>>> = OnceLock::new();
+    ALIASES.get_or_init(Default::default)
+}
+
+/// Make `alias` resolve to `canonical` everywhere a platform is accepted,
+/// e.g. Netmiko-style names such as `cisco_ios_ssh` -> `cisco_ios`.
+///
+/// `canonical` is not checked: an alias to an unknown platform is accepted
+/// and simply fails lookup later with `TEMPLATE_NOT_FOUND`. It may be a
+/// built-in alias or a [`PlatformProvider`] slug. Returns `false`,
+/// registering nothing, if `alias` is a built-in slug or alias or a
+/// provider's name, so built-in names can never be redirected. Registering
+/// the same alias again repoints it.
+pub fn register_platform_alias(alias: &str, canonical: &str) -> bool {
+    if alias.is_empty() || registry::is_builtin_name(alias) || provider(alias).is_some() {
+        return false;
+    }
+    // Built-in targets borrow the registry's slug; anything else is leaked once
+    // per distinct target and shared by every alias pointing at it.
+    let builtin = registry::builtin_platform(canonical).filter(|_| registry::is_builtin_name(canonical));
+    let mut aliases = runtime_aliases().write().unwrap_or_else(|e| e.into_inner());
+    let canonical: &'static str = match builtin.or_else(|| aliases.values().copied().find(|c| *c == canonical)) {
+        Some(interned) => interned,
+        None => Box::leak(canonical.to_owned().into_boxed_str()),
+    };
+    aliases.insert(alias.to_owned(), canonical);
+    true
+}
+
+/// Target of a runtime alias registered with [`register_platform_alias`].
+pub(crate) fn runtime_alias(name: &str) -> Option<&'static str> {
+    let aliases = runtime_aliases().read().unwrap_or_else(|e| e.into_inner());
+    aliases.get(name).copied()
+}
+
+/// A canonical platform, identified by its registry slug (`cisco_ios`,
+/// `juniper_junos`, ...). Aliases such as `cisco_iosxe` resolve on creation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Platform(&'static str);
+
+impl Platform {
+    /// Resolve a platform slug or alias, built-in or from a
+    /// [`PlatformProvider`]. Returns `None` for a built-in name with no
+    /// registered template, or an unknown name.
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        registry::canonical_platform(slug).map(Platform)
+    }
+
+    /// Canonical registry slug.
+    pub fn slug(self) -> &'static str {
+        self.0
+    }
+
+    /// Regex matching a line that is (or starts with) this platform's CLI
+    /// prompt, e.g. `router#show version` or a bare `{master}` on Junos.
+    pub fn default_prompt_regex(self) -> &'static str {
+        match self.0 {
+            // user@host> / user@host# and the {master} / {backup:1} banner line
+            "juniper_junos" => r"^(?:\{\w+(?::\d+)?\}\s*$|[\w.\-]+@[\w.\-]+[>#%])",
+            // *A:hostname# / A:admin@hostname>config# (classic CLI)
+            "alcatel_sros" => r"^\*?[AB]:[\w.\-@]+(?:>[\w.\->]+)?#",
+            // RP/0/RSP0/CPU0:hostname#
+            "cisco_xr" => r"^(?:RP/\d+/\w+/CPU\d+:)?[\w.\-]+(?:\([\w.\-]+\))?#",
+            // hostname# / hostname> / hostname(config-if)#
+            other => match provider(other) {
+                Some(p) => p.prompt_regex(),
+                None => r"^[\w.\-]+(?:\([\w.\-]+\))?[>#]",
+            },
+        }
+    }
+}
+
+/// Canonical registry slug that `input` resolves to, whether `input` is
+/// already canonical or an alias (`cisco_iosxe` -> `cisco_ios`). `None` if no
+/// template is registered for the platform and no [`PlatformProvider`]
+/// claims it.
+pub fn canonical_slug(input: &str) -> Option<&'static str> {
+    registry::canonical_platform(input)
+}
+
+/// Whether `input` is a registered platform alias rather than a canonical
+/// slug. Unknown platforms are not aliases.
+pub fn is_alias(input: &str) -> bool {
+    canonical_slug(input).is_some_and(|canonical| canonical != input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fancy_regex::Regex;
+
+    fn prompt(slug: &str) -> Regex {
+        let p = Platform::from_slug(slug).expect("platform registered");
+        Regex::new(p.default_prompt_regex()).unwrap()
+    }
+
+    fn matches(re: &Regex, line: &str) -> bool {
+        re.is_match(line).unwrap()
+    }
+
+    #[test]
+    fn from_slug_resolves_aliases() {
+        assert_eq!(
+            Platform::from_slug("cisco_iosxe").unwrap().slug(),
+            "cisco_ios"
+        );
+        assert_eq!(
+            Platform::from_slug("nokia_sros").unwrap().slug(),
+            "alcatel_sros"
+        );
+        assert!(Platform::from_slug("nonexistent_os").is_none());
+    }
+
+    #[test]
+    fn alias_introspection() {
+        assert_eq!(canonical_slug("cisco_iosxe"), Some("cisco_ios"));
+        assert!(is_alias("cisco_iosxe"));
+
+        assert_eq!(canonical_slug("cisco_ios"), Some("cisco_ios"));
+        assert!(!is_alias("cisco_ios"));
+
+        assert_eq!(canonical_slug("fortigate"), Some("fortinet"));
+        assert!(is_alias("fortigate"));
+
+        assert_eq!(canonical_slug("nonexistent_os"), None);
+        assert!(!is_alias("nonexistent_os"));
+    }
+
+    struct Custom {
+        slug: &'static str,
+        aliases: &'static [&'static str],
+    }
+
+    impl PlatformProvider for Custom {
+        fn slug(&self) -> &str {
+            self.slug
+        }
+        fn aliases(&self) -> &[&str] {
+            self.aliases
+        }
+        fn prompt_regex(&self) -> &str {
+            r"^\[[\w.\-]+\]\$"
+        }
+    }
+
+    #[test]
+    fn registered_provider_resolves_like_a_builtin() {
+        assert!(register_platform(Box::new(Custom {
+            slug: "vyatta_test",
+            aliases: &["vyos_test"],
+        })));
+
+        let p = Platform::from_slug("vyos_test").unwrap();
+        assert_eq!(p.slug(), "vyatta_test");
+        assert!(is_alias("vyos_test"));
+        assert!(!is_alias("vyatta_test"));
+
+        let re = prompt("vyatta_test");
+        assert!(matches(&re, "[edge01]$ show interfaces"));
+        assert!(!matches(&re, "edge01#"));
+    }
+
+    #[test]
+    fn registration_cannot_shadow_known_names() {
+        let clash = |slug: &'static str, aliases: &'static [&'static str]| register_platform(Box::new(Custom { slug, aliases }));
+        assert!(!clash("cisco_ios", &[]), "built-in slug");
+        assert!(!clash("acme_test", &["cisco_iosxe"]), "built-in alias");
+        assert!(Platform::from_slug("acme_test").is_none(), "rejected provider is not half-registered");
+
+        assert!(clash("widget_test", &[]));
+        assert!(!clash("gadget_test", &["widget_test"]), "earlier provider");
+    }
+
+    #[test]
+    fn runtime_aliases_resolve_but_cannot_shadow_builtins() {
+        assert!(register_platform_alias("cisco_ios_alias_test", "cisco_ios"));
+        assert_eq!(canonical_slug("cisco_ios_alias_test"), Some("cisco_ios"));
+        assert!(is_alias("cisco_ios_alias_test"));
+
+        assert!(register_platform_alias("iosxe_alias_test", "cisco_iosxe"), "targets may be built-in aliases");
+        assert_eq!(canonical_slug("iosxe_alias_test"), Some("cisco_ios"));
+
+        assert!(register_platform_alias("typo_alias_test", "cisco_ios_typo"));
+        assert_eq!(canonical_slug("typo_alias_test"), None, "unknown targets fail lookup later");
+        assert!(register_platform_alias("typo_alias_test_2", "cisco_ios_typo"));
+        assert!(
+            std::ptr::eq(runtime_alias("typo_alias_test").unwrap(), runtime_alias("typo_alias_test_2").unwrap()),
+            "one leaked target shared by both aliases"
+        );
+
+        assert!(!register_platform_alias("cisco_ios", "arista_eos"), "built-in slug");
+        assert!(!register_platform_alias("cisco_iosxe", "arista_eos"), "built-in alias");
+        assert!(!register_platform_alias("", "cisco_ios"));
+        assert_eq!(canonical_slug("cisco_iosxe"), Some("cisco_ios"));
+    }
+
+    #[test]
+    fn cisco_ios_and_nxos_prompts() {
+        for slug in ["cisco_ios", "cisco_nxos"] {
+            let re = prompt(slug);
+            assert!(matches(&re, "core-sw01#show version"));
+            assert!(matches(&re, "core-sw01>"));
+            assert!(matches(&re, "core-sw01(config-if)#"));
+            assert!(!matches(
+                &re,
+                "GigabitEthernet0/1    10.0.0.1   YES manual up   up"
+            ));
+            assert!(!matches(
+                &re,
+                "  Name                             Default RD"
+            ));
+        }
+    }
+
+    #[test]
+    fn cisco_xr_prompt() {
+        let re = prompt("cisco_xr");
+        assert!(matches(&re, "RP/0/RSP0/CPU0:pe1#show version"));
+        assert!(!matches(&re, "Cisco IOS XR Software, Version 7.3.2"));
+    }
+
+    #[test]
+    fn junos_prompts() {
+        let re = prompt("juniper_junos");
+        assert!(matches(&re, "admin@mx1> show version"));
+        assert!(matches(&re, "admin@mx1#"));
+        assert!(matches(&re, "{master}"));
+        assert!(matches(&re, "{master:0}"));
+        assert!(!matches(&re, "Hostname: mx1"));
+        assert!(!matches(&re, "ge-0/0/0    up    up"));
+    }
+
+    #[test]
+    fn nokia_prompt() {
+        let re = prompt("nokia_sros");
+        assert!(matches(&re, "*A:pe-router#"));
+        assert!(matches(&re, "A:pe-router# show system cpu"));
+        assert!(!matches(&re, "System Name            : pe-router"));
+    }
+}
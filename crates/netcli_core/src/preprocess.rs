@@ -0,0 +1,132 @@
+// Input preprocessing — cleans raw captures before they reach the engine.
+
+use std::borrow::Cow;
+
+use crate::parse::ParseOptions;
+
+/// The preprocessing steps enabled by a [`ParseOptions`], run once over the
+/// input in a fixed order:
+///
+/// 1. bare `\r` line separators become `\n` (always on);
+/// 2. lines longer than [`ParseOptions::max_line_len`] are dropped.
+///
+/// Each step sees the output of the previous one, so a step that splits lines
+/// must run before any step that measures or filters them.
+pub(crate) struct Preprocessor {
+    max_line_len: Option<usize>,
+}
+
+/// Cleaned input plus what the steps removed.
+pub(crate) struct Preprocessed<'a> {
+    pub text: Cow<'a, str>,
+    /// Lines dropped for exceeding [`ParseOptions::max_line_len`].
+    pub skipped_lines: usize,
+}
+
+impl Preprocessor {
+    pub(crate) fn from_options(options: &ParseOptions) -> Self {
+        Self {
+            max_line_len: options.max_line_len,
+        }
+    }
+
+    /// Run every enabled step. Borrows `input` when no step changes it.
+    pub(crate) fn run<'a>(&self, input: &'a str) -> Preprocessed<'a> {
+        let mut text = Cow::Borrowed(input);
+        let mut skipped_lines = 0;
+
+        if let Some(out) = normalize_line_endings(&text) {
+            text = Cow::Owned(out);
+        }
+        if let Some(max) = self.max_line_len {
+            if let Some((out, skipped)) = drop_long_lines(&text, max) {
+                text = Cow::Owned(out);
+                skipped_lines = skipped;
+            }
+        }
+
+        Preprocessed {
+            text,
+            skipped_lines,
+        }
+    }
+}
+
+/// Turn bare `\r` separators (old Mac, some console servers) into `\n` so the
+/// engine does not see the whole capture as one line. `\r\n` is left as is.
+/// `None` when there is nothing to rewrite.
+fn normalize_line_endings(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let bare_cr = |i: usize| bytes[i] == b'\r' && bytes.get(i + 1) != Some(&b'\n');
+    if !(0..bytes.len()).any(bare_cr) {
+        return None;
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\r' && chars.peek() != Some(&'\n') {
+            out.push('\n');
+        } else {
+            out.push(c);
+        }
+    }
+    Some(out)
+}
+
+/// Remove lines longer than `max` bytes, returning the kept text and how many
+/// lines were dropped. `None` when nothing is removed.
+fn drop_long_lines(text: &str, max: usize) -> Option<(String, usize)> {
+    if text.lines().all(|l| l.len() <= max) {
+        return None;
+    }
+    let mut skipped = 0;
+    let mut kept = String::with_capacity(text.len().min(max * 64));
+    for line in text.lines() {
+        if line.len() > max {
+            skipped += 1;
+            continue;
+        }
+        kept.push_str(line);
+        kept.push('\n');
+    }
+    Some((kept, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_steps_run_in_order() {
+        let options = ParseOptions {
+            max_line_len: Some(16),
+            ..Default::default()
+        };
+        // The long line is only a separate line once bare CRs are split, so
+        // this also checks that line endings are fixed before lengths are measured.
+        let input = "short one\rxxxxxxxxxxxxxxxxxxxxxxxx\rshort two\r\nlast\n";
+
+        let out = Preprocessor::from_options(&options).run(input);
+
+        assert_eq!(out.text, "short one\nshort two\nlast\n");
+        assert_eq!(out.skipped_lines, 1);
+    }
+
+    #[test]
+    fn clean_input_is_borrowed() {
+        let options = ParseOptions {
+            max_line_len: Some(80),
+            ..Default::default()
+        };
+        let out = Preprocessor::from_options(&options).run("a\nb\n");
+
+        assert!(matches!(out.text, Cow::Borrowed("a\nb\n")));
+        assert_eq!(out.skipped_lines, 0);
+    }
+
+    #[test]
+    fn crlf_is_not_a_bare_cr() {
+        assert_eq!(normalize_line_endings("a\r\nb\r\n"), None);
+        assert_eq!(normalize_line_endings("a\rb\r\n").as_deref(), Some("a\nb\r\n"));
+    }
+}
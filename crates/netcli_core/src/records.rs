@@ -0,0 +1,404 @@
+// Record-set helpers — combining and reshaping parsed records.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::normalize::parse_si_number;
+
+type Record = HashMap<String, String>;
+
+/// A parsed record set that can be persisted and reloaded.
+///
+/// Serializes as an array of objects with keys sorted, so saved files diff
+/// cleanly, and deserializes from the same shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Records(pub Vec<Record>);
+
+impl Records {
+    pub fn iter(&self) -> std::slice::Iter<'_, Record> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Records whose `field` equals `value`, in order.
+    pub fn by_field<'a>(&'a self, field: &'a str, value: &'a str) -> impl Iterator<Item = &'a Record> + 'a {
+        self.0.iter().filter(move |rec| rec.get(field).is_some_and(|v| v == value))
+    }
+}
+
+impl Serialize for Records {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|rec| rec.iter().collect::<BTreeMap<_, _>>()))
+    }
+}
+
+impl From<Vec<Record>> for Records {
+    fn from(records: Vec<Record>) -> Self {
+        Self(records)
+    }
+}
+
+impl From<Records> for Vec<Record> {
+    fn from(records: Records) -> Self {
+        records.0
+    }
+}
+
+impl IntoIterator for Records {
+    type Item = Record;
+    type IntoIter = std::vec::IntoIter<Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Records {
+    type Item = &'a Record;
+    type IntoIter = std::slice::Iter<'a, Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Inner-join two record sets on the field `on`.
+///
+/// Every `left` record is merged with each `right` record whose `on` value is
+/// equal; on field-name conflicts the `right` value wins. Records missing the
+/// `on` field never match. Output follows `left` order, then `right` order.
+pub fn join_records(left: &[Record], right: &[Record], on: &str) -> Vec<Record> {
+    let mut index: HashMap<&str, Vec<&Record>> = HashMap::new();
+    for rec in right {
+        if let Some(key) = rec.get(on) {
+            index.entry(key.as_str()).or_default().push(rec);
+        }
+    }
+
+    let mut joined = Vec::new();
+    for l in left {
+        let Some(matches) = l.get(on).and_then(|key| index.get(key.as_str())) else {
+            continue;
+        };
+        for r in matches {
+            let mut merged = l.clone();
+            merged.extend(r.iter().map(|(k, v)| (k.clone(), v.clone())));
+            joined.push(merged);
+        }
+    }
+    joined
+}
+
+/// Fold records that share the same `on` value into the first of them.
+///
+/// Later records only fill fields that are missing or empty in the first, so
+/// a template can emit one row per table for the same entity (e.g. NX-OS
+/// `show module` prints serials in a separate table) and still return one
+/// record per entity. Records without a non-empty `on` value pass through.
+/// Output follows first-occurrence order.
+pub fn merge_records(records: Vec<Record>, on: &str) -> Vec<Record> {
+    merge_records_indexed(records, on).0
+}
+
+/// [`merge_records`], plus the input index each output record started as.
+pub(crate) fn merge_records_indexed(records: Vec<Record>, on: &str) -> (Vec<Record>, Vec<usize>) {
+    let mut merged: Vec<Record> = Vec::with_capacity(records.len());
+    let mut kept: Vec<usize> = Vec::with_capacity(records.len());
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (n, rec) in records.into_iter().enumerate() {
+        let key = match rec.get(on) {
+            Some(key) if !key.is_empty() => key.clone(),
+            _ => {
+                merged.push(rec);
+                kept.push(n);
+                continue;
+            }
+        };
+        match index.get(&key) {
+            Some(&i) => {
+                let target = &mut merged[i];
+                for (k, v) in rec {
+                    let slot = target.entry(k).or_default();
+                    if slot.is_empty() {
+                        *slot = v;
+                    }
+                }
+            }
+            None => {
+                index.insert(key, merged.len());
+                merged.push(rec);
+                kept.push(n);
+            }
+        }
+    }
+    (merged, kept)
+}
+
+/// Difference between two polls of a monotonically increasing counter.
+///
+/// A decrease is treated as a wrap: of a 32-bit counter when the previous
+/// value fits in 32 bits, otherwise of a 64-bit counter.
+fn counter_delta(prev: u64, curr: u64) -> u64 {
+    if curr >= prev {
+        curr - prev
+    } else if prev <= u64::from(u32::MAX) {
+        (u64::from(u32::MAX) - prev) + curr + 1
+    } else {
+        curr.wrapping_sub(prev)
+    }
+}
+
+/// Per-key counter deltas between two captures of the same command.
+///
+/// Records are paired on the field `on` (e.g. `interface`); each output record
+/// holds `on` plus the delta for every field in `counters` that parses with
+/// [`parse_si_number`] in both captures, so `1.2M` and `1200000` compare
+/// equal. Keys present in only one capture are dropped. Output follows `curr`
+/// order.
+pub fn counter_deltas(
+    prev: &[Record],
+    curr: &[Record],
+    on: &str,
+    counters: &[&str],
+) -> Vec<Record> {
+    let index: HashMap<&str, &Record> = prev
+        .iter()
+        .filter_map(|r| r.get(on).map(|key| (key.as_str(), r)))
+        .collect();
+
+    let counter = |rec: &Record, name: &str| rec.get(name).and_then(|v| parse_si_number(v));
+
+    let mut deltas = Vec::new();
+    for c in curr {
+        let Some(key) = c.get(on) else {
+            continue;
+        };
+        let Some(p) = index.get(key.as_str()) else {
+            continue;
+        };
+        let mut rec = Record::from([(on.to_string(), key.clone())]);
+        for name in counters {
+            if let (Some(before), Some(after)) = (counter(p, name), counter(c, name)) {
+                rec.insert(name.to_string(), counter_delta(before, after).to_string());
+            }
+        }
+        deltas.push(rec);
+    }
+    deltas
+}
+
+/// Render one numeric field of each record as Prometheus text exposition
+/// lines, e.g. `cpu_use_percent{cpu="0"} 28`.
+///
+/// The metric is named `<metric_prefix>_<value_field>` (just `value_field`
+/// when the prefix is empty), with characters Prometheus does not allow
+/// replaced by `_`. Each of `label_fields` becomes a label; a missing field
+/// gives an empty label. Records whose `value_field` is not a finite number
+/// are skipped. Output follows record order, one line per record.
+pub fn records_to_prometheus(
+    metric_prefix: &str,
+    records: &[Record],
+    value_field: &str,
+    label_fields: &[&str],
+) -> String {
+    fn sanitize(name: &str) -> String {
+        name.chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                'a'..='z' | 'A'..='Z' | '_' | ':' => c,
+                '0'..='9' if i > 0 => c,
+                _ => '_',
+            })
+            .collect()
+    }
+    fn escape(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+    }
+
+    let name = match metric_prefix {
+        "" => sanitize(value_field),
+        prefix => sanitize(&format!("{prefix}_{value_field}")),
+    };
+
+    let mut out = String::new();
+    for rec in records {
+        let Some(value) = rec.get(value_field).map(|v| v.trim()) else {
+            continue;
+        };
+        if !value.parse::<f64>().is_ok_and(f64::is_finite) {
+            continue;
+        }
+        let labels: Vec<String> = label_fields
+            .iter()
+            .map(|field| {
+                let v = rec.get(*field).map(String::as_str).unwrap_or_default();
+                format!("{}=\"{}\"", sanitize(field), escape(v))
+            })
+            .collect();
+        if labels.is_empty() {
+            out.push_str(&format!("{name} {value}\n"));
+        } else {
+            out.push_str(&format!("{name}{{{}}} {value}\n", labels.join(",")));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(pairs: &[(&str, &str)]) -> Record {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn records_select_by_field_and_serialize_sorted() {
+        let recs = Records::from(vec![
+            rec(&[("vlan", "10"), ("interface", "Gi0/1")]),
+            rec(&[("vlan", "20"), ("interface", "Gi0/2")]),
+            rec(&[("vlan", "10"), ("interface", "Gi0/3")]),
+        ]);
+        let hits: Vec<&str> = recs.by_field("vlan", "10").map(|r| r["interface"].as_str()).collect();
+        assert_eq!(hits, ["Gi0/1", "Gi0/3"]);
+        assert_eq!(recs.by_field("missing", "10").count(), 0);
+
+        let json = serde_json::to_string(&recs).unwrap();
+        assert!(json.starts_with(r#"[{"interface":"Gi0/1","vlan":"10"}"#), "{json}");
+    }
+
+    #[test]
+    fn prometheus_lines_skip_non_numeric_and_escape_labels() {
+        let recs = vec![
+            rec(&[("interface", "Gi0/1"), ("description", "to \"core\""), ("crc", "12")]),
+            rec(&[("interface", "Gi0/2"), ("crc", "n/a")]),
+            rec(&[("interface", "Gi0/3"), ("crc", "0.5")]),
+        ];
+        let text = records_to_prometheus("netcli", &recs, "crc", &["interface", "description"]);
+        assert_eq!(
+            text,
+            "netcli_crc{interface=\"Gi0/1\",description=\"to \\\"core\\\"\"} 12\n\
+             netcli_crc{interface=\"Gi0/3\",description=\"\"} 0.5\n"
+        );
+        assert_eq!(records_to_prometheus("", &recs[..1], "crc", &[]), "crc 12\n");
+        assert_eq!(records_to_prometheus("if-stats", &recs[..1], "crc", &[]), "if_stats_crc 12\n");
+    }
+
+    #[test]
+    fn join_brief_and_description_on_interface() {
+        let brief = vec![
+            rec(&[("interface", "Gi0/0"), ("ip_address", "10.1.1.1"), ("status", "up")]),
+            rec(&[("interface", "Gi0/1"), ("ip_address", "unassigned"), ("status", "down")]),
+            rec(&[("interface", "Gi0/2"), ("ip_address", "10.1.2.1"), ("status", "up")]),
+        ];
+        let descriptions = vec![
+            rec(&[("interface", "Gi0/2"), ("description", "WAN"), ("status", "up")]),
+            rec(&[("interface", "Gi0/0"), ("description", "LAN"), ("status", "admin down")]),
+            rec(&[("interface", "Lo0"), ("description", "router-id")]),
+        ];
+
+        let joined = join_records(&brief, &descriptions, "interface");
+
+        assert_eq!(joined.len(), 2, "Gi0/1 and Lo0 have no partner");
+        assert_eq!(joined[0]["interface"], "Gi0/0");
+        assert_eq!(joined[0]["ip_address"], "10.1.1.1");
+        assert_eq!(joined[0]["description"], "LAN");
+        assert_eq!(joined[0]["status"], "admin down", "right side wins on conflict");
+        assert_eq!(joined[1]["interface"], "Gi0/2");
+        assert_eq!(joined[1]["description"], "WAN");
+    }
+
+    #[test]
+    fn join_skips_records_without_key_field() {
+        let left = vec![rec(&[("name", "x")])];
+        let right = vec![rec(&[("name", "x")])];
+        assert!(join_records(&left, &right, "interface").is_empty());
+    }
+
+    #[test]
+    fn join_emits_one_row_per_matching_pair() {
+        let left = vec![rec(&[("vlan", "10"), ("name", "users")])];
+        let right = vec![
+            rec(&[("vlan", "10"), ("port", "Gi0/1")]),
+            rec(&[("vlan", "10"), ("port", "Gi0/2")]),
+        ];
+        let joined = join_records(&left, &right, "vlan");
+        assert_eq!(joined.len(), 2);
+        assert_eq!(joined[0]["port"], "Gi0/1");
+        assert_eq!(joined[1]["port"], "Gi0/2");
+        assert!(joined.iter().all(|r| r["name"] == "users"));
+    }
+
+    #[test]
+    fn merge_fills_blanks_from_later_tables() {
+        let recs = vec![
+            rec(&[("module", "1"), ("model", "N7K-F248XP-25E"), ("serial", "")]),
+            rec(&[("module", "5"), ("model", "N7K-SUP2E"), ("serial", "")]),
+            rec(&[("module", ""), ("model", "orphan")]),
+            rec(&[("module", "5"), ("model", ""), ("serial", "JAF1812ARBG")]),
+            rec(&[("module", "1"), ("model", "ignored"), ("serial", "JAF1717AAHP"), ("sw", "8.4(2)")]),
+        ];
+
+        let merged = merge_records(recs, "module");
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0]["module"], "1");
+        assert_eq!(merged[0]["model"], "N7K-F248XP-25E", "first non-empty value wins");
+        assert_eq!(merged[0]["serial"], "JAF1717AAHP");
+        assert_eq!(merged[0]["sw"], "8.4(2)");
+        assert_eq!(merged[1]["serial"], "JAF1812ARBG");
+        assert_eq!(merged[2]["model"], "orphan", "records without a key pass through");
+    }
+
+    #[test]
+    fn counter_deltas_between_snapshots() {
+        let prev = vec![
+            rec(&[("interface", "Gi0/1"), ("input_packets", "1000"), ("output_packets", "500")]),
+            rec(&[("interface", "Gi0/2"), ("input_packets", "4294967200"), ("output_packets", "10")]),
+            rec(&[("interface", "Gi0/3"), ("input_packets", "7")]),
+        ];
+        let curr = vec![
+            rec(&[("interface", "Gi0/2"), ("input_packets", "100"), ("output_packets", "10")]),
+            rec(&[("interface", "Gi0/1"), ("input_packets", "1600"), ("output_packets", "N/A")]),
+            rec(&[("interface", "Gi0/4"), ("input_packets", "1")]),
+        ];
+
+        let deltas = counter_deltas(&prev, &curr, "interface", &["input_packets", "output_packets"]);
+
+        assert_eq!(deltas.len(), 2, "Gi0/3 and Gi0/4 appear in only one capture");
+        assert_eq!(deltas[0]["interface"], "Gi0/2");
+        assert_eq!(deltas[0]["input_packets"], "196", "32-bit wrap: 95 to the top, then 101");
+        assert_eq!(deltas[0]["output_packets"], "0");
+        assert_eq!(deltas[1]["interface"], "Gi0/1");
+        assert_eq!(deltas[1]["input_packets"], "600");
+        assert!(!deltas[1].contains_key("output_packets"), "non-numeric counters are skipped");
+    }
+
+    #[test]
+    fn counter_deltas_mix_raw_and_unit_formats() {
+        let prev = vec![rec(&[("interface", "Gi0/1"), ("input_bytes", "1.2M")])];
+        let curr = vec![rec(&[("interface", "Gi0/1"), ("input_bytes", "1500000")])];
+
+        let deltas = counter_deltas(&prev, &curr, "interface", &["input_bytes"]);
+        assert_eq!(deltas[0]["input_bytes"], "300000");
+    }
+
+    #[test]
+    fn counter_delta_wraps_64_bit() {
+        assert_eq!(counter_delta(u64::MAX - 4, 5), 10);
+        assert_eq!(counter_delta(u64::from(u32::MAX), 0), 1);
+        assert_eq!(counter_delta(10, 10), 0);
+    }
+}
@@ -14,6 +14,11 @@ pub(crate) struct RegistryEntry {
     pub command_key: String,
     pub template: String,
     pub shape: String,
+    /// Fold records sharing this field's value into one, for templates that
+    /// read the same entity out of several tables (see
+    /// [`crate::records::merge_records`]).
+    #[serde(rename = "mergeOn", default)]
+    pub merge_on: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -23,36 +28,202 @@ struct RegistryFile {
 
 type Key = (String, String);
 
+fn load_entries() -> Vec<RegistryEntry> {
+    let json = RESOURCES
+        .get_file("registry.json")
+        .and_then(|f| f.contents_utf8())
+        .expect("embedded registry.json missing");
+
+    let file: RegistryFile = serde_json::from_str(json).expect("registry.json is not valid JSON");
+    file.templates
+}
+
+/// Build the lookup map. On duplicate keys the first entry wins, so a merge
+/// mistake appended later in the file cannot silently shadow a known template.
+fn build_registry(entries: Vec<RegistryEntry>) -> HashMap<Key, RegistryEntry> {
+    let mut map = HashMap::with_capacity(entries.len());
+    for e in entries {
+        map.entry((e.platform.clone(), e.command_key.clone()))
+            .or_insert(e);
+    }
+    map
+}
+
+fn find_duplicates(entries: &[RegistryEntry]) -> Vec<Key> {
+    let mut seen: HashMap<Key, usize> = HashMap::new();
+    for e in entries {
+        *seen
+            .entry((e.platform.clone(), e.command_key.clone()))
+            .or_default() += 1;
+    }
+    let mut dups: Vec<Key> = seen
+        .into_iter()
+        .filter(|(_, n)| *n > 1)
+        .map(|(k, _)| k)
+        .collect();
+    dups.sort();
+    dups
+}
+
+/// `(platform, command_key)` pairs that appear more than once in the embedded
+/// `registry.json`. Empty on a healthy registry; intended for CI checks.
+pub fn duplicate_registry_keys() -> Vec<(String, String)> {
+    find_duplicates(&load_entries())
+}
+
 fn registry() -> &'static HashMap<Key, RegistryEntry> {
     static INSTANCE: OnceLock<HashMap<Key, RegistryEntry>> = OnceLock::new();
-    INSTANCE.get_or_init(|| {
-        let json = RESOURCES
-            .get_file("registry.json")
-            .and_then(|f| f.contents_utf8())
-            .expect("embedded registry.json missing");
+    INSTANCE.get_or_init(|| build_registry(load_entries()))
+}
 
-        let file: RegistryFile =
-            serde_json::from_str(json).expect("registry.json is not valid JSON");
+/// Summary of registry coverage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryStats {
+    /// Total number of (platform, command key) entries.
+    pub total_entries: usize,
+    /// Command key count per canonical platform, sorted by platform slug.
+    pub platforms: Vec<(String, usize)>,
+}
 
-        file.templates
-            .into_iter()
-            .map(|e| ((e.platform.clone(), e.command_key.clone()), e))
-            .collect()
-    })
+/// Report how many command keys each canonical platform has.
+pub fn registry_stats() -> RegistryStats {
+    let reg = registry();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (platform, _) in reg.keys() {
+        *counts.entry(platform.as_str()).or_default() += 1;
+    }
+
+    let mut platforms: Vec<(String, usize)> =
+        counts.into_iter().map(|(p, n)| (p.to_string(), n)).collect();
+    platforms.sort();
+
+    RegistryStats {
+        total_entries: reg.len(),
+        platforms,
+    }
+}
+
+/// Canonical slugs of every platform with at least one embedded template,
+/// sorted.
+pub fn list_supported_platforms() -> Vec<&'static str> {
+    let mut platforms: Vec<&'static str> = registry()
+        .keys()
+        .map(|(platform, _)| resolve_platform(platform))
+        .collect();
+    platforms.sort_unstable();
+    platforms.dedup();
+    platforms
 }
 
+/// Command keys with a template for `platform`, sorted. Aliases (e.g.
+/// `cisco_iosxe`) list their canonical platform's commands, and a registered
+/// [`crate::platform::PlatformProvider`] lists those of the platform it takes
+/// templates from. Empty for an unknown platform.
+pub fn list_commands_for_platform(platform: &str) -> Vec<&'static str> {
+    let canonical = match crate::platform::provider(resolve_platform(platform)).and_then(|p| p.templates_from()) {
+        Some(base) if builtin_platform(platform).is_none() => resolve_platform(base),
+        _ => resolve_platform(platform),
+    };
+    let mut commands: Vec<&'static str> = registry()
+        .keys()
+        .filter(|(p, _)| resolve_platform(p) == canonical)
+        .map(|(_, command_key)| command_key.as_str())
+        .collect();
+    commands.sort_unstable();
+    commands.dedup();
+    commands
+}
+
+/// Canonical slug for `platform`: a runtime alias from
+/// [`crate::platform::register_platform_alias`] first, then the built-in
+/// aliases.
 fn resolve_platform(platform: &str) -> &str {
+    builtin_alias(crate::platform::runtime_alias(platform).unwrap_or(platform))
+}
+
+fn builtin_alias(platform: &str) -> &str {
     match platform {
         "cisco_iosxe" => "cisco_ios",
         "nokia_sros" => "alcatel_sros",
         "cisco_iosxr" => "cisco_xr",
+        "fortios" | "fortigate" => "fortinet",
+        "panos" | "palo_alto" => "paloalto_panos",
         other => other,
     }
 }
 
+/// Whether `name` is a built-in slug or alias, ignoring runtime aliases.
+pub(crate) fn is_builtin_name(name: &str) -> bool {
+    builtin_alias(name) != name || registry().keys().any(|(platform, _)| platform == name)
+}
+
+/// Canonical slug for a built-in `platform` (aliases resolved), borrowed
+/// from the registry so it lives for `'static`. `None` if no template is
+/// registered.
+pub(crate) fn builtin_platform(platform: &str) -> Option<&'static str> {
+    let canonical = resolve_platform(platform);
+    registry()
+        .values()
+        .find(|e| e.platform == canonical)
+        .map(|e| e.platform.as_str())
+}
+
+/// [`builtin_platform`], falling back to a registered
+/// [`crate::platform::PlatformProvider`]'s slug.
+pub(crate) fn canonical_platform(platform: &str) -> Option<&'static str> {
+    builtin_platform(platform).or_else(|| crate::platform::provider(resolve_platform(platform)).map(|p| p.slug()))
+}
+
+/// Registry entry for `(platform, command_key)`. A provider platform is looked
+/// up under the built-in platform it takes templates from.
 pub(crate) fn lookup(platform: &str, command_key: &str) -> Option<&'static RegistryEntry> {
     let canonical = resolve_platform(platform);
-    registry().get(&(canonical.into(), command_key.into()))
+    registry().get(&(canonical.into(), command_key.into())).or_else(|| {
+        let base = crate::platform::provider(canonical)?.templates_from()?;
+        registry().get(&(resolve_platform(base).into(), command_key.into()))
+    })
+}
+
+/// Strip a redundant leading platform slug from a key, e.g.
+/// `cisco_ios_show_version` -> `show_version`. Both the slug as given and its
+/// canonical form are recognised.
+fn strip_platform_prefix<'k>(platform: &str, command_key: &'k str) -> Option<&'k str> {
+    [platform, resolve_platform(platform)]
+        .iter()
+        .find_map(|slug| command_key.strip_prefix(slug)?.strip_prefix('_'))
+        .filter(|rest| !rest.is_empty())
+}
+
+/// Row filters a command accepts without changing its output format, e.g.
+/// `show mac address-table dynamic`. A qualified key resolves to the
+/// unqualified command's template.
+const QUALIFIED_COMMANDS: &[(&str, &[&str])] = &[("show_mac_address_table", &["dynamic", "static"])];
+
+/// Split a qualified key into its unqualified command key and qualifier,
+/// e.g. `show_mac_address_table_dynamic` -> `("show_mac_address_table",
+/// "dynamic")`.
+fn split_qualifier(command_key: &str) -> Option<(&'static str, &'static str)> {
+    QUALIFIED_COMMANDS.iter().find_map(|&(base, qualifiers)| {
+        let rest = command_key.strip_prefix(base)?.strip_prefix('_')?;
+        qualifiers.iter().find(|&&q| q == rest).map(|&q| (base, q))
+    })
+}
+
+/// [`lookup`], falling back to the key with a redundant platform prefix
+/// removed, then to the unqualified key (see [`QUALIFIED_COMMANDS`]). The
+/// flag reports whether the platform prefix fallback was used.
+pub(crate) fn lookup_lenient(
+    platform: &str,
+    command_key: &str,
+) -> Option<(&'static RegistryEntry, bool)> {
+    if let Some(entry) = lookup(platform, command_key) {
+        return Some((entry, false));
+    }
+    if let Some(entry) = strip_platform_prefix(platform, command_key).and_then(|k| lookup(platform, k)) {
+        return Some((entry, true));
+    }
+    let (base, _) = split_qualifier(command_key)?;
+    lookup(platform, base).map(|entry| (entry, false))
 }
 
 fn expand_abbreviation(word: &str) -> String {
@@ -121,6 +292,73 @@ pub(crate) fn lookup_command(
     (expanded, None)
 }
 
+/// Every step of platform alias resolution, command normalization and
+/// registry lookup for one `(platform, command)` pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// Platform after alias resolution (e.g. `cisco_iosxe` -> `cisco_ios`).
+    pub canonical_platform: String,
+    /// Command key with abbreviations expanded (`sho ip int br` -> `show_ip_interface_brief`).
+    pub normalized_key: String,
+    /// Command key with only `sh`/`sho` expanded (`show_ip_int_br`).
+    pub raw_key: String,
+    /// The key that resolved, or `normalized_key` when neither did.
+    pub command_key: String,
+    /// Whether a redundant platform prefix was stripped from the key.
+    pub key_adjusted: bool,
+    /// Whether a registry entry was found.
+    pub matched: bool,
+    /// Template path of the matched entry.
+    pub template_path: Option<String>,
+    /// Output filters after the command (`| include hostname` ->
+    /// `["include hostname"]`), whitespace-collapsed but otherwise as typed.
+    /// They are still folded into the key; a non-empty list means the output
+    /// is a filtered subset of what the command normally prints.
+    pub pipe_modifiers: Vec<String>,
+    /// Row filter dropped to reach the template, e.g. `dynamic` for
+    /// `show mac address-table dynamic`, which parses with the
+    /// `show_mac_address_table` template.
+    pub qualifier: Option<String>,
+}
+
+fn pipe_modifiers(command: &str) -> Vec<String> {
+    command
+        .split('|')
+        .skip(1)
+        .map(|stage| stage.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|stage| !stage.is_empty())
+        .collect()
+}
+
+/// Dry-run the command API lookup without parsing anything.
+pub fn resolve(platform: &str, command: &str) -> Resolution {
+    let (command_key, entry) = lookup_command(platform, command);
+    let (entry, key_adjusted) = match entry {
+        Some(e) => (Some(e), false),
+        None => match lookup_lenient(platform, &command_key) {
+            Some((e, adjusted)) => (Some(e), adjusted),
+            None => (None, false),
+        },
+    };
+    let qualifier = entry
+        .zip(split_qualifier(&command_key))
+        .filter(|(e, (base, _))| e.command_key == *base)
+        .map(|(_, (_, q))| q.to_string());
+    Resolution {
+        canonical_platform: canonical_platform(platform)
+            .unwrap_or_else(|| resolve_platform(platform))
+            .to_string(),
+        normalized_key: normalize_command(command),
+        raw_key: normalize_raw(command),
+        command_key: entry.map_or(command_key, |e| e.command_key.clone()),
+        key_adjusted,
+        matched: entry.is_some(),
+        template_path: entry.map(|e| e.template.clone()),
+        pipe_modifiers: pipe_modifiers(command),
+        qualifier,
+    }
+}
+
 pub(crate) fn load_template_text(entry: &RegistryEntry) -> Option<&'static str> {
     RESOURCES
         .get_file(&entry.template)
@@ -269,6 +507,10 @@ mod tests {
         assert_eq!(resolve_platform("arista_eos"), "arista_eos");
         assert_eq!(resolve_platform("nokia_sros"), "alcatel_sros");
         assert_eq!(resolve_platform("cisco_iosxr"), "cisco_xr");
+        assert_eq!(resolve_platform("fortios"), "fortinet");
+        assert_eq!(resolve_platform("fortigate"), "fortinet");
+        assert_eq!(resolve_platform("panos"), "paloalto_panos");
+        assert_eq!(resolve_platform("palo_alto"), "paloalto_panos");
     }
 
     #[test]
@@ -311,4 +553,191 @@ mod tests {
         let reg = registry();
         assert!(reg.len() > 900, "expected 900+ registry entries, got {}", reg.len());
     }
+
+    #[test]
+    fn registry_stats_totals_match_registry() {
+        let stats = registry_stats();
+        assert_eq!(stats.total_entries, registry().len());
+        assert_eq!(
+            stats.platforms.iter().map(|(_, n)| n).sum::<usize>(),
+            stats.total_entries
+        );
+        assert!(stats.platforms.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn registry_stats_cisco_ios_count() {
+        let stats = registry_stats();
+        let (_, ios) = stats
+            .platforms
+            .iter()
+            .find(|(p, _)| p == "cisco_ios")
+            .expect("cisco_ios missing from stats");
+        assert!(*ios > 100, "expected 100+ cisco_ios keys, got {ios}");
+        assert!(stats.platforms.iter().all(|(p, _)| p != "cisco_iosxe"));
+    }
+
+    fn entry(platform: &str, command_key: &str, template: &str) -> RegistryEntry {
+        RegistryEntry {
+            platform: platform.into(),
+            command_key: command_key.into(),
+            template: template.into(),
+            shape: "list".into(),
+            merge_on: None,
+        }
+    }
+
+    #[test]
+    fn duplicate_keys_detected_in_crafted_registry() {
+        let entries = vec![
+            entry("cisco_ios", "show_version", "templates/a.textfsm"),
+            entry("cisco_ios", "show_clock", "templates/b.textfsm"),
+            entry("cisco_ios", "show_version", "templates/c.textfsm"),
+            entry("arista_eos", "show_version", "templates/d.textfsm"),
+        ];
+        assert_eq!(
+            find_duplicates(&entries),
+            vec![("cisco_ios".to_string(), "show_version".to_string())]
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_first_entry_wins() {
+        let entries = vec![
+            entry("cisco_ios", "show_version", "templates/first.textfsm"),
+            entry("cisco_ios", "show_version", "templates/second.textfsm"),
+        ];
+        let map = build_registry(entries);
+        assert_eq!(map.len(), 1);
+        let key = ("cisco_ios".to_string(), "show_version".to_string());
+        assert_eq!(map[&key].template, "templates/first.textfsm");
+    }
+
+    /// The canonical command keys listed under "Command keys" in the README.
+    fn documented_command_keys() -> Vec<&'static str> {
+        let readme = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../README.md"));
+        let section = readme
+            .split("\n## Command keys\n")
+            .nth(1)
+            .and_then(|rest| rest.split("\n## ").next())
+            .expect("README has a Command keys section");
+        section.split('`').skip(1).step_by(2).collect()
+    }
+
+    #[test]
+    fn every_documented_command_key_has_a_template() {
+        let keys = documented_command_keys();
+        assert!(!keys.is_empty());
+
+        let orphans: Vec<&str> = keys
+            .into_iter()
+            .filter(|key| !registry().keys().any(|(_, k)| k == key))
+            .collect();
+        assert!(orphans.is_empty(), "documented command keys with no registry entry on any platform: {orphans:?}");
+    }
+
+    #[test]
+    fn supported_platforms_are_sorted_canonical_slugs() {
+        let platforms = list_supported_platforms();
+        assert!(platforms.windows(2).all(|w| w[0] < w[1]), "sorted and de-duplicated");
+        assert!(platforms.contains(&"cisco_ios"));
+        assert!(platforms.contains(&"juniper_junos"));
+        assert!(!platforms.contains(&"cisco_iosxe"), "aliases are not listed separately");
+        assert_eq!(platforms.len(), registry_stats().platforms.len());
+    }
+
+    #[test]
+    fn commands_for_platform_resolve_aliases() {
+        let ios = list_commands_for_platform("cisco_ios");
+        assert!(ios.windows(2).all(|w| w[0] < w[1]), "sorted and de-duplicated");
+        assert!(ios.contains(&"show_version"));
+        assert_eq!(list_commands_for_platform("cisco_iosxe"), ios);
+        assert_eq!(
+            ios.len(),
+            registry_stats().platforms.iter().find(|(p, _)| p == "cisco_ios").unwrap().1
+        );
+        assert!(list_commands_for_platform("nonexistent_os").is_empty());
+    }
+
+    #[test]
+    fn embedded_registry_has_no_duplicate_keys() {
+        assert_eq!(duplicate_registry_keys(), Vec::<Key>::new());
+    }
+
+    #[test]
+    fn resolve_abbreviated_command() {
+        let r = resolve("cisco_ios", "sho ip int br");
+        assert_eq!(r.canonical_platform, "cisco_ios");
+        assert_eq!(r.normalized_key, "show_ip_interface_brief");
+        assert_eq!(r.raw_key, "show_ip_int_br");
+        assert_eq!(r.command_key, "show_ip_interface_brief");
+        assert!(r.matched);
+        assert_eq!(
+            r.template_path.as_deref(),
+            Some("templates/cisco_ios/show_ip_interface_brief.textfsm")
+        );
+    }
+
+    #[test]
+    fn resolve_reports_pipe_modifiers_separately() {
+        let r = resolve("cisco_ios", "show running-config | include hostname");
+        assert_eq!(r.command_key, "show_running_config_include_hostname");
+        assert_eq!(r.pipe_modifiers, ["include hostname"]);
+
+        let r = resolve("cisco_ios", "sho ip int br |  ex  unas | count");
+        assert_eq!(r.pipe_modifiers, ["ex unas", "count"]);
+
+        assert!(resolve("cisco_ios", "show version").pipe_modifiers.is_empty());
+        assert!(resolve("cisco_ios", "show version |").pipe_modifiers.is_empty());
+    }
+
+    #[test]
+    fn resolve_reports_mac_table_qualifier() {
+        for (command, qualifier) in [
+            ("show mac address-table dynamic", "dynamic"),
+            ("sh mac address-table static", "static"),
+        ] {
+            let r = resolve("cisco_ios", command);
+            assert!(r.matched, "{command}");
+            assert_eq!(r.command_key, "show_mac_address_table");
+            assert_eq!(r.qualifier.as_deref(), Some(qualifier));
+            assert!(!r.key_adjusted);
+        }
+
+        let r = resolve("cisco_ios", "show mac address-table");
+        assert!(r.matched);
+        assert_eq!(r.qualifier, None);
+        assert!(!resolve("cisco_ios", "show mac address-table bogus").matched);
+    }
+
+    #[test]
+    fn resolve_reports_alias_and_miss() {
+        let r = resolve("cisco_iosxe", "show magic unicorn");
+        assert_eq!(r.canonical_platform, "cisco_ios");
+        assert_eq!(r.command_key, "show_magic_unicorn");
+        assert!(!r.matched);
+        assert!(!r.key_adjusted);
+        assert_eq!(r.template_path, None);
+    }
+
+    #[test]
+    fn resolve_strips_redundant_platform_prefix() {
+        let r = resolve("cisco_ios", "cisco_ios show version");
+        assert_eq!(r.command_key, "show_version");
+        assert!(r.matched);
+        assert!(r.key_adjusted);
+
+        let r = resolve("cisco_iosxe", "cisco_ios_show_version");
+        assert_eq!(r.command_key, "show_version");
+        assert!(r.key_adjusted);
+    }
+
+    #[test]
+    fn strip_platform_prefix_requires_full_slug() {
+        assert_eq!(strip_platform_prefix("cisco_ios", "cisco_ios_show_version"), Some("show_version"));
+        assert_eq!(strip_platform_prefix("cisco_iosxe", "cisco_ios_show_version"), Some("show_version"));
+        assert_eq!(strip_platform_prefix("cisco_ios", "cisco_iosshow_version"), None);
+        assert_eq!(strip_platform_prefix("cisco_ios", "cisco_ios_"), None);
+        assert_eq!(strip_platform_prefix("cisco_ios", "show_version"), None);
+    }
 }
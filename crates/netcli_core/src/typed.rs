@@ -0,0 +1,281 @@
+//! Deserialize parsed records straight into caller-defined types.
+//!
+//! Every field value is a string, so instead of going through an untyped
+//! JSON value each field is handed to serde as its own deserializer that
+//! parses on demand: numbers and `true`/`false` for numeric and `bool`
+//! fields (floats only in the [`coercible_number`] grammar, so `6.60%`,
+//! `1.234,56` or `inf` are errors), the empty string as `None`, `List`
+//! values as sequences of their items, and the raw text for everything else.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Unexpected, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::normalize::coercible_number;
+use crate::parse::{list_items, ListItems};
+
+/// A record that did not fit the target type.
+#[derive(Debug)]
+pub(crate) struct FieldError {
+    /// The offending field, when serde's error can be pinned to one.
+    pub(crate) field: Option<String>,
+    pub(crate) message: String,
+}
+
+impl FieldError {
+    fn in_field(mut self, field: &str) -> Self {
+        self.field.get_or_insert_with(|| field.to_string());
+        self
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl de::Error for FieldError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            field: None,
+            message: msg.to_string(),
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            message: format!("missing field `{field}`"),
+        }
+    }
+}
+
+/// One field value; parses itself into whatever type the visitor asks for.
+#[derive(Clone, Copy)]
+struct FieldDeserializer<'a> {
+    field: &'a str,
+    value: &'a str,
+    /// The engine's items when the field is a `List` value.
+    items: Option<&'a [String]>,
+}
+
+impl FieldDeserializer<'_> {
+    fn invalid(&self, expected: &dyn de::Expected) -> FieldError {
+        de::Error::invalid_value(Unexpected::Str(self.value), expected)
+    }
+}
+
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FieldError> {
+                let result = match self.value.parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(self.invalid(&visitor)),
+                };
+                result.map_err(|e| e.in_field(self.field))
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'_> {
+    type Error = FieldError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FieldError> {
+        visitor.visit_str(self.value).map_err(|e: FieldError| e.in_field(self.field))
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FieldError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FieldError> {
+        let result = match coercible_number(self.value).and_then(|n| n.as_f64()) {
+            Some(v) => visitor.visit_f64(v),
+            None => Err(self.invalid(&visitor)),
+        };
+        result.map_err(|e| e.in_field(self.field))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FieldError> {
+        let result = if self.value.is_empty() { visitor.visit_none() } else { visitor.visit_some(self) };
+        result.map_err(|e| e.in_field(self.field))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FieldError> {
+        let field = self.field;
+        let items: Vec<&str> = match self.items {
+            Some(items) => items.iter().map(String::as_str).collect(),
+            None => list_items(self.value),
+        };
+        let items = items.into_iter().map(|value| FieldDeserializer {
+            field,
+            value,
+            items: None,
+        });
+        SeqDeserializer::new(items).deserialize_any(visitor).map_err(|e| e.in_field(field))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, FieldError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FieldError> {
+        visitor.visit_enum(self.value.into_deserializer()).map_err(|e: FieldError| e.in_field(self.field))
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, FieldError> for FieldDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Deserialize one record, taking `List` items from `lists`. Fields the
+/// record has but `T` lacks are ignored unless `T` denies unknown fields.
+pub(crate) fn from_record<T: DeserializeOwned>(
+    record: &HashMap<String, String>,
+    lists: &ListItems,
+) -> Result<T, FieldError> {
+    let fields = record.iter().map(|(field, value)| {
+        let items = lists.get(field).map(Vec::as_slice);
+        (field.as_str(), FieldDeserializer { field, value, items })
+    });
+    T::deserialize(MapDeserializer::new(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Up,
+        Down,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Row {
+        name: String,
+        mtu: u32,
+        load: f64,
+        enabled: bool,
+        vlan: Option<u16>,
+        status: Status,
+        members: Vec<String>,
+    }
+
+    fn rec(fields: &[(&str, &str)]) -> HashMap<String, String> {
+        fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn fields_parse_into_their_target_types() {
+        let fields = rec(&[
+            ("name", "100"),
+            ("mtu", "1500"),
+            ("load", "0.25"),
+            ("enabled", "true"),
+            ("vlan", ""),
+            ("status", "up"),
+            ("members", "[Eth1, Eth2]"),
+            ("extra", "ignored"),
+        ]);
+        let row: Row = from_record(&fields, &ListItems::new()).unwrap();
+        assert_eq!(
+            row,
+            Row {
+                name: "100".into(),
+                mtu: 1500,
+                load: 0.25,
+                enabled: true,
+                vlan: None,
+                status: Status::Up,
+                members: vec!["Eth1".into(), "Eth2".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn list_fields_use_the_engine_items() {
+        #[derive(Debug, Deserialize)]
+        struct Log {
+            message: Vec<String>,
+        }
+        let items = vec!["Gi0/1, down".to_string(), "up".to_string()];
+        let lists = ListItems::from([("message".to_string(), items)]);
+        let log: Log = from_record(&rec(&[("message", "[Gi0/1, down, up]")]), &lists).unwrap();
+        assert_eq!(log.message, ["Gi0/1, down", "up"]);
+    }
+
+    #[test]
+    fn errors_name_the_field() {
+        let base = [
+            ("name", "Gi0/1"),
+            ("mtu", "1500"),
+            ("load", "1"),
+            ("enabled", "false"),
+            ("vlan", "10"),
+            ("status", "down"),
+            ("members", ""),
+        ];
+        let mut bad = rec(&base);
+        bad.insert("mtu".into(), "1500 bytes".into());
+        let err = from_record::<Row>(&bad, &ListItems::new()).unwrap_err();
+        assert_eq!(err.field.as_deref(), Some("mtu"));
+        assert!(err.message.contains("1500 bytes"), "{}", err.message);
+
+        for load in ["6.60%", "1.234,56", "inf", "1e3"] {
+            let mut bad = rec(&base);
+            bad.insert("load".into(), load.into());
+            let err = from_record::<Row>(&bad, &ListItems::new()).unwrap_err();
+            assert_eq!(err.field.as_deref(), Some("load"), "{load:?} must not coerce");
+        }
+
+        let mut missing = rec(&base);
+        missing.remove("status");
+        let err = from_record::<Row>(&missing, &ListItems::new()).unwrap_err();
+        assert_eq!(err.field.as_deref(), Some("status"));
+
+        let mut unknown = rec(&base);
+        unknown.insert("status".into(), "admin-down".into());
+        let err = from_record::<Row>(&unknown, &ListItems::new()).unwrap_err();
+        assert_eq!(err.field.as_deref(), Some("status"));
+    }
+}
@@ -13,33 +13,37 @@ fn records(v: &Value) -> &Vec<Value> {
     v["records"].as_array().unwrap()
 }
 
-#[test]
-fn cisco_ios_show_version_parses_real_output() {
-    let output = include_str!("fixtures/cisco_ios/show_version.txt");
-    let json_str = netcli_core::parse_json("cisco_ios", "show_version", output);
-    let v = parse_envelope(&json_str);
-
-    assert_eq!(v["ok"], true);
-    assert_eq!(v["platform"], "cisco_ios");
-    assert_eq!(v["commandKey"], "show_version");
-    assert!(v["records"].is_array());
-
-    let records = v["records"].as_array().unwrap();
-    assert!(!records.is_empty(), "expected at least one parsed record");
+/// Generate a golden test that parses a fixture through `parse_json` and
+/// asserts a successful envelope with the expected record count.
+///
+/// ```ignore
+/// parse_fixture! {
+///     test_name: "platform", "command_key", "platform/fixture.txt", 3;
+/// }
+/// ```
+macro_rules! parse_fixture {
+    ($($name:ident: $platform:expr, $command_key:expr, $fixture:expr, $count:expr;)+) => {
+        $(
+            #[test]
+            fn $name() {
+                let output = include_str!(concat!("fixtures/", $fixture));
+                let v = parse_envelope(&netcli_core::parse_json($platform, $command_key, output));
+
+                assert_success(&v);
+                assert_eq!(v["platform"], $platform);
+                assert_eq!(v["commandKey"], $command_key);
+                assert_eq!(records(&v).len(), $count, "record count for {}", $fixture);
+            }
+        )+
+    };
 }
 
-#[test]
-fn juniper_junos_show_version_parses_real_output() {
-    let output = include_str!("fixtures/juniper_junos/show_version.txt");
-    let json_str = netcli_core::parse_json("juniper_junos", "show_version", output);
-    let v = parse_envelope(&json_str);
-
-    assert_eq!(v["ok"], true);
-    assert_eq!(v["platform"], "juniper_junos");
-    assert_eq!(v["commandKey"], "show_version");
-
-    let records = v["records"].as_array().unwrap();
-    assert!(!records.is_empty(), "expected at least one parsed record");
+parse_fixture! {
+    cisco_ios_show_version_parses_real_output: "cisco_ios", "show_version", "cisco_ios/show_version.txt", 1;
+    juniper_junos_show_version_parses_real_output: "juniper_junos", "show_version", "juniper_junos/show_version.txt", 1;
+    cisco_nxos_show_cdp_neighbors_fixture: "cisco_nxos", "show_cdp_neighbors", "cisco_nxos/show_cdp_neighbors.txt", 2;
+    cisco_ios_show_vrf_fixture: "cisco_ios", "show_vrf", "cisco_ios/show_vrf.txt", 3;
+    cisco_iosxe_show_inventory_fixture: "cisco_iosxe", "show_inventory", "cisco_iosxe/show_inventory.txt", 11;
 }
 
 // --- additional platform golden tests ---
@@ -61,6 +65,178 @@ fn arista_eos_show_version_parses_real_output() {
     assert_eq!(recs[0]["sys_mac"], "001c.7300.0001");
 }
 
+#[test]
+fn arista_eos_show_version_uptime_and_memory() {
+    let output = include_str!("fixtures/arista_eos/show_version.txt");
+    let options = netcli_core::ParseOptions {
+        normalize: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options("arista_eos", "show_version", output, &options));
+
+    assert_success(&v);
+    let rec = &records(&v)[0];
+    assert_eq!(rec["uptime"], "2 weeks, 3 days, 12 hours and 34 minutes");
+    assert_eq!(rec["uptime_seconds"], "1514040");
+    assert_eq!(rec["total_memory"], "8069292");
+    assert_eq!(rec["free_memory"], "5765088");
+    assert_eq!(rec["hardware_revision"], "01.02");
+    assert_eq!(rec["hw_version"], "01.02", "kept alongside hardware_revision");
+
+    let v = parse_envelope(&netcli_core::parse_json("arista_eos", "show_version", output));
+    assert!(records(&v)[0].get("uptime_seconds").is_none(), "derived only under normalize");
+}
+
+#[test]
+fn parse_options_builder_sets_only_what_is_asked() {
+    let output = include_str!("fixtures/arista_eos/show_version.txt");
+    let options = netcli_core::ParseOptions::builder()
+        .normalize(true)
+        .expect_single(true)
+        .build();
+    assert!(!options.include_meta, "unset options keep their defaults");
+    assert_eq!(options.max_line_len, None);
+
+    let recs = netcli_core::parse_records_with_options("arista_eos", "show_version", output, &options).unwrap();
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("uptime_seconds").unwrap(), "1514040");
+
+    let options = netcli_core::ParseOptions::builder().normalize_fields(true).include_meta(true).build();
+    assert!(options.normalize && options.include_meta);
+    let recs = netcli_core::parse_records_opts("arista_eos", "show_version", output, &options).unwrap();
+    assert_eq!(recs[0].get("uptime_seconds").unwrap(), "1514040");
+}
+
+/// Hostname from the prompt, filled down into every interface; interfaces
+/// without an address are dropped.
+const PROMPTED_INTERFACES: &str = r"Value Filldown HOSTNAME ([\w.-]+)
+Value Required INTERFACE (\S+)
+Value Required IP_ADDRESS (\d+\.\d+\.\d+\.\d+/\d+)
+Value MTU (\d+)
+
+Start
+  ^${HOSTNAME}#
+  ^\S+\s+is\s+ -> Continue.Record
+  ^${INTERFACE}\s+is\s+
+  ^\s+Internet\s+address\s+is\s+${IP_ADDRESS}
+  ^\s+MTU\s+${MTU}\s+bytes
+";
+
+#[test]
+fn engine_filldown_and_required_on_multi_block_output() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces_prompted.txt");
+    let recs = netcli_core::run_template(PROMPTED_INTERFACES, output, &netcli_core::ParseOptions::default()).unwrap();
+
+    let names: Vec<&str> = recs.iter().map(|r| r.get("interface").unwrap().as_str()).collect();
+    assert_eq!(names, ["GigabitEthernet0/0", "Loopback0"], "Gi0/1 has no address and is Required-dropped");
+    assert!(recs.iter().all(|r| r.get("hostname").unwrap() == "edge-rtr1"), "prompt hostname fills down");
+    assert_eq!(recs[1].get("ip_address").unwrap(), "10.255.0.1/32");
+    assert_eq!(recs[0].get("mtu").unwrap(), "1500");
+    assert_eq!(recs[1].get("mtu").unwrap(), "1514", "non-Filldown values reset at each record");
+}
+
+#[test]
+fn prepared_template_matches_one_shot_parse() {
+    let options = netcli_core::ParseOptions::default();
+    let prepared = netcli_core::prepare("cisco_ios", "show_interfaces").unwrap();
+    for output in [
+        include_str!("fixtures/cisco_ios/show_interfaces.txt"),
+        include_str!("fixtures/cisco_ios/show_interfaces_queue_drops.txt"),
+    ] {
+        assert_eq!(
+            prepared.parse_json(output, &options),
+            netcli_core::parse_json("cisco_ios", "show_interfaces", output)
+        );
+    }
+
+    let err = prepared.parse_records("", &options).unwrap_err();
+    assert_eq!(err.code(), "INVALID_INPUT");
+    assert_eq!(netcli_core::prepare("cisco_ios", "show_nonexistent").err().unwrap().code(), "TEMPLATE_NOT_FOUND");
+}
+
+#[test]
+fn interface_bandwidth_normalized_to_bps() {
+    let options = netcli_core::ParseOptions::builder().normalize(true).build();
+    let cases = [
+        ("cisco_ios", "show_interfaces", include_str!("fixtures/cisco_ios/show_interfaces.txt")),
+        ("cisco_nxos", "show_interface", include_str!("fixtures/cisco_nxos/show_interface.txt")),
+        ("arista_eos", "show_interfaces", include_str!("fixtures/arista_eos/show_interfaces.txt")),
+    ];
+    for (platform, command_key, output) in cases {
+        let recs = netcli_core::parse_records_with_options(platform, command_key, output, &options).unwrap();
+        let gig = &recs[0];
+        assert_eq!(gig.get("bandwidth_bps").unwrap(), "1000000000", "{platform}");
+        assert!(gig.get("bandwidth").unwrap().starts_with("1000000 "), "raw value kept on {platform}");
+    }
+
+    let recs = netcli_core::parse_records_with_options(
+        "cisco_ios",
+        "show_interfaces",
+        include_str!("fixtures/cisco_ios/show_interfaces_queue_drops.txt"),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(recs[1].get("bandwidth_bps").unwrap(), "1544000", "T1 serial link");
+}
+
+#[test]
+fn interface_load_interval_captured_in_seconds() {
+    let options = netcli_core::ParseOptions::builder().normalize(true).build();
+    let recs = netcli_core::parse_records_with_options(
+        "cisco_ios",
+        "show_interfaces",
+        include_str!("fixtures/cisco_ios/show_interfaces_load_interval.txt"),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(recs[0].get("load_interval").unwrap(), "30 second");
+    assert_eq!(recs[0].get("load_interval_seconds").unwrap(), "30");
+    assert_eq!(recs[0].get("input_rate_bps").unwrap(), "8120000");
+    assert_eq!(recs[1].get("load_interval_seconds").unwrap(), "120");
+
+    let recs = netcli_core::parse_records_with_options(
+        "cisco_nxos",
+        "show_interface",
+        include_str!("fixtures/cisco_nxos/show_interface.txt"),
+        &options,
+    )
+    .unwrap();
+    // The last window printed wins, matching the rates captured alongside it.
+    assert_eq!(recs[0].get("load_interval_seconds").unwrap(), "300");
+    assert_eq!(recs[0].get("bandwidth_input_rate").unwrap(), "1408");
+    // No rate lines at all: fall back to the 5-minute default.
+    assert_eq!(recs[1].get("load_interval").unwrap(), "");
+    assert_eq!(recs[1].get("load_interval_seconds").unwrap(), "300");
+
+    // Junos templates capture no load interval, so none is invented.
+    let recs = netcli_core::parse_records_with_options(
+        "juniper_junos",
+        "show_interfaces",
+        include_str!("fixtures/juniper_junos/show_interfaces.txt"),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(recs.len(), 3);
+    assert!(recs.iter().all(|r| !r.contains_key("load_interval_seconds")));
+}
+
+#[test]
+fn arista_eos_show_mlag_interfaces_detail() {
+    let output = include_str!("fixtures/arista_eos/show_mlag_interfaces_detail.txt");
+    let recs =
+        netcli_core::parse_command_records("arista_eos", "show mlag interfaces detail", output).unwrap();
+
+    assert_eq!(recs.len(), 4);
+    assert_eq!(recs[0].get("mlag_id").unwrap(), "1");
+    assert_eq!(recs[0].get("status").unwrap(), "active-full");
+    assert_eq!(recs[0].get("local_interface").unwrap(), "Po1");
+    assert_eq!(recs[0].get("peer_interface").unwrap(), "Po1");
+    assert_eq!(recs[0].get("local_state").unwrap(), "up");
+    assert_eq!(recs[0].get("peer_state").unwrap(), "up");
+    assert_eq!(recs[2].get("status").unwrap(), "active-partial");
+    assert_eq!(recs[2].get("peer_state").unwrap(), "down");
+}
+
 #[test]
 fn cisco_nxos_show_version_parses_real_output() {
     let output = include_str!("fixtures/cisco_nxos/show_version.txt");
@@ -91,6 +267,118 @@ fn cisco_ios_show_version_field_values() {
     assert_eq!(recs[0]["version"], "12.2(55)SE10");
     assert_eq!(recs[0]["software_image"], "C3750-IPSERVICESK9-M");
     assert_eq!(recs[0]["running_image"], "c3750-ipservicesk9-mz.122-55.SE10.bin");
+    assert_eq!(recs[0]["feature"], serde_json::json!([]), "no license table on this platform");
+}
+
+#[test]
+fn records_round_trip_through_serde() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let recs = netcli_core::Records::from(netcli_core::parse_records("cisco_ios", "show_version", output).unwrap());
+
+    let saved = serde_json::to_string(&recs).unwrap();
+    let loaded: netcli_core::Records = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded, recs);
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), saved, "serialization is stable");
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded.by_field("hostname", "Router01").count(), 1);
+}
+
+#[test]
+fn cisco_ios_show_version_feature_licenses() {
+    let output = include_str!("fixtures/cisco_ios/show_version_license.txt");
+    let v = parse_envelope(&netcli_core::parse_json("cisco_ios", "show_version", output));
+
+    assert_success(&v);
+    let rec = &records(&v)[0];
+    assert_eq!(rec["feature"], serde_json::json!(["ipbase", "security", "uc", "data"]));
+    assert_eq!(rec["license_level"], serde_json::json!(["ipbasek9", "securityk9", "None", "datak9"]));
+    assert_eq!(rec["license_type"], serde_json::json!(["Permanent", "Permanent", "None", "RightToUse"]));
+    assert_eq!(rec["config_register"], "0x2102", "parsing resumes after the license table");
+    assert_eq!(rec["serial"], serde_json::json!(["FTX1840AKQ2"]));
+}
+
+#[test]
+fn cisco_ios_show_license_udi() {
+    let output = include_str!("fixtures/cisco_ios/show_license_udi.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_license_udi", output).unwrap();
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("pid").unwrap(), "CISCO2911/K9");
+    assert_eq!(recs[0].get("sn").unwrap(), "FTX1840AKQ2");
+    assert_eq!(recs[0].get("udi").unwrap(), "CISCO2911/K9:FTX1840AKQ2");
+
+    // Smart-licensing releases print one UDI line; the HA list repeats it.
+    let output = include_str!("fixtures/cisco_ios/show_license_udi_smart.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_license_udi", output).unwrap();
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("pid").unwrap(), "C9300-48P");
+    assert_eq!(recs[0].get("sn").unwrap(), "FOC2248X1QZ");
+    assert_eq!(recs[0].get("udi").unwrap(), "PID:C9300-48P,SN:FOC2248X1QZ");
+}
+
+#[test]
+fn juniper_junos_show_clock_from_system_uptime() {
+    let output = include_str!("fixtures/juniper_junos/show_system_uptime.txt");
+    let recs = netcli_core::parse_records("juniper_junos", "show_clock", output).unwrap();
+
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("time").unwrap(), "14:02:11");
+    assert_eq!(recs[0].get("timezone").unwrap(), "UTC");
+    assert_eq!(recs[0].get("sync_source").unwrap(), "NTP CLOCK");
+    assert_eq!(recs[0].get("sync_state").unwrap(), "");
+}
+
+#[test]
+fn juniper_junos_show_chassis_power() {
+    let output = include_str!("fixtures/juniper_junos/show_chassis_power.txt");
+    let recs = netcli_core::parse_command_records("juniper_junos", "show chassis power", output).unwrap();
+
+    assert_eq!(recs.len(), 3, "system power budget is not a PEM");
+    let pem0 = &recs[0];
+    assert_eq!(pem0.get("pem").unwrap(), "0");
+    assert_eq!(pem0.get("state").unwrap(), "Online");
+    assert_eq!(pem0.get("capacity").unwrap(), "5100");
+    assert_eq!(pem0.get("dc_output").unwrap(), "642");
+    assert_eq!(recs[2].get("state").unwrap(), "Empty");
+    assert_eq!(recs[2].get("capacity").unwrap(), "");
+}
+
+#[test]
+fn juniper_junos_show_chassis_environment_pem() {
+    let output = include_str!("fixtures/juniper_junos/show_chassis_environment_pem.txt");
+    let recs = netcli_core::parse_command_records("juniper_junos", "show chassis environment pem", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    let pem1 = &recs[1];
+    assert_eq!(pem1.get("pem").unwrap(), "1");
+    assert_eq!(pem1.get("state").unwrap(), "Online");
+    assert_eq!(pem1.get("dc_output").unwrap(), "588");
+    assert_eq!(pem1.get("dc_voltage").unwrap(), "53.50");
+    assert_eq!(pem1.get("temperature").unwrap(), "38");
+    assert_eq!(recs[2].get("dc_output").unwrap(), "", "a PEM without AC input reports no output");
+}
+
+#[test]
+fn juniper_junos_show_system_alarms() {
+    let output = include_str!("fixtures/juniper_junos/show_system_alarms.txt");
+    let recs = netcli_core::parse_command_records("juniper_junos", "show system alarms", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    assert_eq!(recs[0].get("time").unwrap(), "2024-03-11 09:14:22 UTC");
+    assert_eq!(recs[0].get("severity").unwrap(), "Major");
+    assert_eq!(recs[0].get("class").unwrap(), "Major");
+    assert_eq!(recs[0].get("description").unwrap(), "PEM 1 Not Powered");
+    assert_eq!(recs[2].get("severity").unwrap(), "Minor");
+    assert_eq!(recs[2].get("description").unwrap(), "Autorecovery information needs to be saved");
+
+    let chassis = netcli_core::parse_command_records("juniper_junos", "show chassis alarms", output).unwrap();
+    assert_eq!(chassis, recs);
+}
+
+#[test]
+fn juniper_junos_show_system_alarms_none_active() {
+    let output = include_str!("fixtures/juniper_junos/show_system_alarms_none.txt");
+    let recs = netcli_core::parse_command_records("juniper_junos", "show system alarms", output).unwrap();
+    assert!(recs.is_empty());
 }
 
 // --- multi-record parsing tests ---
@@ -118,6 +406,46 @@ fn cisco_ios_show_interfaces_parses_multiple_records() {
     assert_eq!(recs[1]["ip_address"], "192.168.1.1");
 }
 
+#[test]
+fn cisco_ios_show_interfaces_rate_fields() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_interfaces", output).unwrap();
+
+    let gi01 = &recs[0];
+    assert_eq!(gi01.get("interface").unwrap(), "GigabitEthernet0/1");
+    assert_eq!(gi01.get("input_rate_bps").unwrap(), "50000");
+    assert_eq!(gi01.get("output_rate_bps").unwrap(), "60000");
+    assert_eq!(gi01.get("input_rate_pps").unwrap(), "40");
+    assert_eq!(gi01.get("output_rate_pps").unwrap(), "45");
+    // pre-existing rate fields are still populated
+    assert_eq!(gi01.get("input_rate").unwrap(), "50000");
+    assert_eq!(gi01.get("output_pps").unwrap(), "45");
+
+    assert_eq!(recs[1].get("input_rate_bps").unwrap(), "0");
+}
+
+#[test]
+fn cisco_ios_show_interfaces_queue_drops() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces_queue_drops.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_interfaces", output).unwrap();
+
+    assert_eq!(recs.len(), 2);
+    let wan = &recs[0];
+    assert_eq!(wan.get("interface").unwrap(), "GigabitEthernet0/0/1");
+    assert_eq!(wan.get("queue_strategy").unwrap(), "Class-based queueing");
+    assert_eq!(wan.get("input_queue_drops").unwrap(), "118");
+    assert_eq!(wan.get("total_output_drops").unwrap(), "48213");
+    assert_eq!(wan.get("output_queue_drops").unwrap(), "", "size/max output queue has no drop counter");
+    // pre-existing queue fields are still populated
+    assert_eq!(wan.get("queue_drops").unwrap(), "118");
+    assert_eq!(wan.get("queue_output_drops").unwrap(), "48213");
+
+    let serial = &recs[1];
+    assert_eq!(serial.get("queue_strategy").unwrap(), "weighted fair");
+    assert_eq!(serial.get("output_queue_drops").unwrap(), "912");
+    assert_eq!(serial.get("total_output_drops").unwrap(), "912");
+}
+
 #[test]
 fn cisco_ios_show_ip_bgp_summary_parses_multiple_neighbors() {
     let output = include_str!("fixtures/cisco_ios/show_ip_bgp_summary.txt");
@@ -304,8 +632,290 @@ fn command_api_unknown_command_returns_template_not_found() {
     assert_eq!(v["error"]["code"], "TEMPLATE_NOT_FOUND");
 }
 
+#[test]
+fn unregistered_command_key_is_template_not_found() {
+    let v = parse_envelope(&netcli_core::parse_json("cisco_ios", "show_magic_unicorn", "output"));
+    assert_eq!(v["ok"], false);
+    assert_eq!(v["error"]["code"], "TEMPLATE_NOT_FOUND");
+
+    let err = netcli_core::parse_records("cisco_ios", "show_magic_unicorn", "output").unwrap_err();
+    assert_eq!(err.code(), "TEMPLATE_NOT_FOUND");
+    assert!(
+        !netcli_core::ParseError::CODES.contains(&"UNKNOWN_COMMAND"),
+        "an unknown command is reported as a missing template"
+    );
+}
+
+#[test]
+fn command_api_meta_matched_without_records() {
+    let output = "Port      Name               Status       Reason               Err-disabled Vlans\n";
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_command_json_with_options(
+        "cisco_ios",
+        "show interfaces status err-disabled",
+        output,
+        &options,
+    ));
+
+    assert_success(&v);
+    assert!(records(&v).is_empty());
+    assert_eq!(v["meta"]["matched"], true);
+    assert_eq!(v["meta"]["recordCount"], 0);
+}
+
+#[test]
+fn command_api_meta_not_matched_for_unrecognized_output() {
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_command_json_with_options(
+        "cisco_ios",
+        "show interfaces status err-disabled",
+        "completely unrelated text\n\n",
+        &options,
+    ));
+
+    assert_success(&v);
+    assert_eq!(v["meta"]["matched"], false);
+    assert_eq!(v["meta"]["recordCount"], 0);
+}
+
+#[test]
+fn command_api_meta_matched_with_records() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces_status_err_disabled.txt");
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_command_json_with_options(
+        "cisco_ios",
+        "show interfaces status err-disabled",
+        output,
+        &options,
+    ));
+
+    assert_eq!(v["meta"]["matched"], true);
+    assert_eq!(v["meta"]["recordCount"], 3);
+    assert_eq!(v["meta"]["unmatchedLines"], 0);
+}
+
+#[test]
+fn parse_records_meta_tells_no_match_from_nothing_to_report() {
+    let (recs, meta) = netcli_core::parse_records_meta(
+        "cisco_ios",
+        "show_interfaces_status_err_disabled",
+        "completely unrelated text\nmore garbage here\n\n",
+    )
+    .unwrap();
+    assert!(recs.is_empty());
+    assert!(!meta.matched_any_rule);
+    assert_eq!(meta.record_count, 0);
+    assert_eq!(meta.unmatched_lines, 2, "the blank line is matched by the template");
+
+    let header = "Port      Name               Status       Reason               Err-disabled Vlans\n";
+    let (recs, meta) =
+        netcli_core::parse_records_meta("cisco_ios", "show_interfaces_status_err_disabled", header).unwrap();
+    assert!(recs.is_empty());
+    assert!(meta.matched_any_rule, "a header with no rows is a legitimate empty result");
+    assert_eq!(meta.unmatched_lines, 0);
+
+    let output = include_str!("fixtures/cisco_ios/show_interfaces_status_err_disabled.txt");
+    let (recs, meta) =
+        netcli_core::parse_records_meta("cisco_ios", "show_interfaces_status_err_disabled", output).unwrap();
+    assert_eq!(meta.record_count, recs.len());
+    assert_eq!(meta.record_count, 3);
+}
+
+#[test]
+fn parse_records_typed_deserializes_into_caller_structs() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Interface {
+        interface: String,
+        mtu: u32,
+        input_rate_bps: u64,
+        prefix_length: Option<u8>,
+        vlan_id: Option<u16>,
+    }
+
+    let output = include_str!("fixtures/cisco_ios/show_interfaces.txt");
+    let ifaces: Vec<Interface> = netcli_core::parse_records_typed("cisco_ios", "show_interfaces", output).unwrap();
+    assert_eq!(ifaces.len(), 2);
+    assert_eq!(ifaces[0].interface, "GigabitEthernet0/1");
+    assert_eq!(ifaces[0].mtu, 1500);
+    assert_eq!(ifaces[0].input_rate_bps, 50000);
+    assert_eq!(ifaces[0].prefix_length, Some(24));
+    assert_eq!(ifaces[0].vlan_id, None, "empty fields are None");
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Wrong {
+        #[allow(dead_code)]
+        bandwidth: u64,
+    }
+    let err = netcli_core::parse_records_typed::<Wrong>("cisco_ios", "show_interfaces", output).unwrap_err();
+    assert_eq!(err.code(), "DESERIALIZE_ERROR");
+    match err {
+        netcli_core::ParseError::Deserialize { record, field, message } => {
+            assert_eq!(record, 0);
+            assert_eq!(field.as_deref(), Some("bandwidth"));
+            assert!(message.contains("1000000 Kbit"), "{message}");
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let err = netcli_core::parse_records_typed::<Interface>("nonexistent_os", "show_interfaces", output).unwrap_err();
+    assert_eq!(err.code(), "TEMPLATE_NOT_FOUND", "parse errors pass through unchanged");
+}
+
+#[test]
+fn redundant_platform_prefix_in_key_is_stripped() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+
+    for (platform, key) in [("cisco_ios", "cisco_ios_show_version"), ("cisco_iosxe", "cisco_ios_show_version")] {
+        let v = parse_envelope(&netcli_core::parse_json_with_options(platform, key, output, &options));
+        assert_success(&v);
+        assert_eq!(v["commandKey"], "show_version");
+        assert_eq!(v["meta"]["keyAdjusted"], true);
+        assert_eq!(records(&v).len(), 1);
+    }
+
+    let v = parse_envelope(&netcli_core::parse_command_json_with_options(
+        "cisco_ios",
+        "cisco_ios show version",
+        output,
+        &options,
+    ));
+    assert_success(&v);
+    assert_eq!(v["commandKey"], "show_version");
+    assert_eq!(v["meta"]["keyAdjusted"], true);
+
+    let v = parse_envelope(&netcli_core::parse_json_with_options("cisco_ios", "show_version", output, &options));
+    assert_eq!(v["meta"]["keyAdjusted"], false);
+}
+
+#[test]
+fn max_line_len_skips_pathological_line() {
+    let fixture = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let output = format!("{}\n{fixture}", "A".repeat(2 * 1024 * 1024));
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        max_line_len: Some(4096),
+        ..Default::default()
+    };
+
+    let started = std::time::Instant::now();
+    let v = parse_envelope(&netcli_core::parse_json_with_options(
+        "cisco_ios",
+        "show_ip_interface_brief",
+        &output,
+        &options,
+    ));
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert_success(&v);
+    assert_eq!(records(&v).len(), 12);
+    assert_eq!(v["meta"]["skippedLines"], 1);
+}
+
+#[test]
+fn max_line_len_unset_skips_nothing() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options(
+        "cisco_ios",
+        "show_ip_interface_brief",
+        output,
+        &options,
+    ));
+
+    assert_eq!(records(&v).len(), 12);
+    assert_eq!(v["meta"]["skippedLines"], 0);
+}
+
+#[test]
+fn omit_empty_fields_drops_only_empty_values() {
+    let output = include_str!("fixtures/cisco_ios/show_bgp_vpnv4_unicast_all.txt");
+    let options = netcli_core::ParseOptions {
+        omit_empty_fields: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options(
+        "cisco_ios",
+        "show_bgp_vpnv4_unicast_all",
+        output,
+        &options,
+    ));
+
+    assert_success(&v);
+    assert_eq!(records(&v).len(), 6);
+    let rec = records(&v)[0].as_object().unwrap();
+    assert_eq!(rec["network"], "10.10.1.0/24");
+    assert_eq!(rec["metric"], "0", "\"0\" is a value, not an empty field");
+    assert_eq!(rec["weight"], "32768");
+    assert!(!rec.contains_key("local_pref"));
+    assert!(!rec.contains_key("path"));
+
+    let v = parse_envelope(&netcli_core::parse_json("cisco_ios", "show_bgp_vpnv4_unicast_all", output));
+    assert_eq!(records(&v)[0]["local_pref"], "", "fields are kept by default");
+}
+
+#[test]
+fn bare_cr_line_endings_parse_like_lf() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let expected = netcli_core::parse_records("cisco_ios", "show_version", output).unwrap();
+
+    let cr_only = output.replace("\r\n", "\n").replace('\n', "\r");
+    let recs = netcli_core::parse_records("cisco_ios", "show_version", &cr_only).unwrap();
+    assert_eq!(recs, expected);
+}
+
 // --- parse_records (non-JSON) API tests ---
 
+#[test]
+fn parse_single_returns_the_one_record() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let options = netcli_core::ParseOptions {
+        expect_single: true,
+        ..Default::default()
+    };
+    let rec = netcli_core::parse_single_with_options("cisco_ios", "show_version", output, &options)
+        .unwrap()
+        .expect("show_version yields one record");
+    assert_eq!(rec, netcli_core::parse_records("cisco_ios", "show_version", output).unwrap()[0]);
+}
+
+#[test]
+fn parse_single_expect_single_rejects_multiple_records() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces.txt");
+    let count = netcli_core::parse_records("cisco_ios", "show_interfaces", output).unwrap().len();
+    assert!(count > 1);
+
+    let first = netcli_core::parse_single("cisco_ios", "show_interfaces", output).unwrap();
+    assert!(first.is_some(), "without expect_single the first record is returned");
+
+    let options = netcli_core::ParseOptions {
+        expect_single: true,
+        ..Default::default()
+    };
+    let err = netcli_core::parse_single_with_options("cisco_ios", "show_interfaces", output, &options).unwrap_err();
+    assert_eq!(err.code(), "UNEXPECTED_RECORD_COUNT");
+    assert!(matches!(err, netcli_core::ParseError::UnexpectedRecordCount(n) if n == count));
+
+    let v = parse_envelope(&netcli_core::parse_json_with_options("cisco_ios", "show_interfaces", output, &options));
+    assert_eq!(v["ok"], false);
+    assert_eq!(v["error"]["code"], "UNEXPECTED_RECORD_COUNT");
+}
+
 #[test]
 fn parse_records_returns_ok_vec() {
     let output = include_str!("fixtures/cisco_ios/show_version.txt");
@@ -367,46 +977,150 @@ fn parse_records_multi_record() {
     assert_eq!(recs[1].get("interface").unwrap(), "GigabitEthernet0/2");
 }
 
-// --- parse_command_records (non-JSON) API tests ---
-
 #[test]
-fn parse_command_records_returns_ok_vec() {
-    let output = include_str!("fixtures/cisco_ios/show_version.txt");
-    let result = netcli_core::parse_command_records("cisco_ios", "show version", output);
+fn parse_records_with_drops_down_interfaces() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let recs = netcli_core::parse_records_with("cisco_ios", "show_ip_interface_brief", output, |rec| {
+        rec.get("status").map(String::as_str) == Some("up")
+    })
+    .unwrap();
 
-    assert!(result.is_ok());
-    let recs = result.unwrap();
-    assert_eq!(recs.len(), 1);
-    assert_eq!(recs[0].get("hostname").unwrap(), "Router01");
+    assert_eq!(recs.len(), 8);
+    assert!(recs.iter().all(|r| r.get("status").unwrap() == "up"));
+    assert_eq!(recs[2].get("interface").unwrap(), "GigabitEthernet1/0");
 }
 
 #[test]
-fn parse_command_records_normalizes_input() {
-    let output = include_str!("fixtures/cisco_ios/show_version.txt");
-    let recs = netcli_core::parse_command_records("cisco_ios", "  Show  Version  ", output).unwrap();
+fn parse_records_with_keeps_mutations() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let recs = netcli_core::parse_records_with("cisco_ios", "show_ip_interface_brief", output, |rec| {
+        rec.insert("site".into(), "lab".into());
+        rec.remove("proto");
+        true
+    })
+    .unwrap();
 
-    assert_eq!(recs.len(), 1);
-    assert_eq!(recs[0].get("hostname").unwrap(), "Router01");
+    assert_eq!(recs.len(), 12);
+    assert!(recs.iter().all(|r| r.get("site").unwrap() == "lab"));
+    assert!(recs.iter().all(|r| !r.contains_key("proto")));
 }
 
+#[cfg(feature = "mpsc")]
 #[test]
-fn parse_command_records_empty_command_error() {
-    let result = netcli_core::parse_command_records("cisco_ios", "", "output");
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err().code(), "INVALID_INPUT");
+fn parse_to_channel_sends_same_records() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let worker = std::thread::spawn(move || {
+        netcli_core::parse_to_channel("cisco_ios", "show_ip_interface_brief", output, tx)
+    });
+    let streamed: Vec<_> = rx.iter().collect();
+    worker.join().unwrap().unwrap();
+
+    let expected = netcli_core::parse_records("cisco_ios", "show_ip_interface_brief", output).unwrap();
+    assert_eq!(streamed, expected);
 }
 
-// --- edge case tests ---
+#[cfg(feature = "mpsc")]
+#[test]
+fn parse_to_channel_honours_options() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let options = netcli_core::ParseOptions::builder().expect_single(true).build();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let err = netcli_core::parse_to_channel_with_options("cisco_ios", "show_ip_interface_brief", output, &options, tx)
+        .unwrap_err();
+
+    assert_eq!(err.code(), "UNEXPECTED_RECORD_COUNT");
+    assert!(rx.iter().next().is_none());
+}
 
+#[cfg(feature = "mpsc")]
 #[test]
-fn unrecognized_only_input_returns_empty_records() {
-    let garbage = "this is random text\nthat matches nothing\nin the template\n";
-    let json_str = netcli_core::parse_json("cisco_ios", "show_version", garbage);
-    let v = parse_envelope(&json_str);
+fn parse_to_channel_propagates_errors() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let err = netcli_core::parse_to_channel("nonexistent_os", "show_version", "x", tx).unwrap_err();
 
-    assert_eq!(v["ok"], true, "unrecognized lines should not cause an error");
-    let recs = records(&v);
-    assert!(recs.is_empty(), "should return empty records for unrecognized input");
+    assert_eq!(err.code(), "TEMPLATE_NOT_FOUND");
+    assert!(rx.iter().next().is_none());
+}
+
+#[test]
+fn parse_records_with_propagates_errors() {
+    let err = netcli_core::parse_records_with("cisco_ios", "", "text", |_| true).unwrap_err();
+    assert_eq!(err.code(), "INVALID_INPUT");
+}
+
+#[test]
+fn validate_template_describes_a_shipped_template() {
+    let text = include_str!("../resources/templates/juniper_junos/show_system_alarms.textfsm");
+    let info = netcli_core::validate_template(text).unwrap();
+    let names: Vec<&str> = info.values.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["TIME", "SEVERITY", "CLASS", "DESCRIPTION"]);
+    assert!(info.values.iter().all(|v| v.modifiers.is_empty()));
+    assert_eq!(info.states, ["Start"]);
+}
+
+#[test]
+fn csv_output_has_template_header_and_one_row_per_record() {
+    let output = include_str!("fixtures/juniper_junos/show_system_alarms.txt");
+    let csv = netcli_core::parse_records_csv("juniper_junos", "show_system_alarms", output).unwrap();
+    let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "time,severity,class,description");
+    assert_eq!(lines[1], "2024-03-11 09:14:22 UTC,Major,Major,PEM 1 Not Powered");
+
+    let none = include_str!("fixtures/juniper_junos/show_system_alarms_none.txt");
+    let csv = netcli_core::parse_records_csv("juniper_junos", "show_system_alarms", none).unwrap();
+    assert_eq!(csv, "time,severity,class,description\r\n", "zero records still give the header");
+
+    let output = include_str!("fixtures/cisco_ios/show_version_license.txt");
+    let options = netcli_core::ParseOptions::builder().csv_list_delimiter("|").build();
+    let csv = netcli_core::parse_records_csv_with_options("cisco_ios", "show_version", output, &options).unwrap();
+    assert!(csv.contains(",feature,license_level,license_type"), "{csv}");
+    assert!(csv.contains(",ipbase|security|uc|data,ipbasek9|securityk9|None|datak9,"), "{csv}");
+    assert!(csv.contains(",\"1 year, 12 weeks, 3 days, 4 hours, 51 minutes\","), "commas force quoting: {csv}");
+}
+
+// --- parse_command_records (non-JSON) API tests ---
+
+#[test]
+fn parse_command_records_returns_ok_vec() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let result = netcli_core::parse_command_records("cisco_ios", "show version", output);
+
+    assert!(result.is_ok());
+    let recs = result.unwrap();
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("hostname").unwrap(), "Router01");
+}
+
+#[test]
+fn parse_command_records_normalizes_input() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "  Show  Version  ", output).unwrap();
+
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("hostname").unwrap(), "Router01");
+}
+
+#[test]
+fn parse_command_records_empty_command_error() {
+    let result = netcli_core::parse_command_records("cisco_ios", "", "output");
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().code(), "INVALID_INPUT");
+}
+
+// --- edge case tests ---
+
+#[test]
+fn unrecognized_only_input_returns_empty_records() {
+    let garbage = "this is random text\nthat matches nothing\nin the template\n";
+    let json_str = netcli_core::parse_json("cisco_ios", "show_version", garbage);
+    let v = parse_envelope(&json_str);
+
+    assert_eq!(v["ok"], true, "unrecognized lines should not cause an error");
+    let recs = records(&v);
+    assert!(recs.is_empty(), "should return empty records for unrecognized input");
 }
 
 #[test]
@@ -430,6 +1144,191 @@ fn json_envelope_success_shape() {
     assert!(v["commandKey"].is_string());
     assert!(v["records"].is_array());
     assert!(v.get("error").is_none() || v["error"].is_null());
+    assert!(v.get("meta").is_none(), "meta is opt-in");
+}
+
+#[test]
+fn json_envelope_meta_reports_alias_resolution() {
+    let output = include_str!("fixtures/cisco_iosxe/show_version.txt");
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options(
+        "cisco_iosxe",
+        "show_version",
+        output,
+        &options,
+    ));
+
+    assert_success(&v);
+    assert_eq!(v["platform"], "cisco_iosxe");
+    assert_eq!(v["meta"]["resolvedPlatform"], "cisco_ios");
+    let path = v["meta"]["templatePath"].as_str().unwrap();
+    assert!(path.ends_with(".textfsm"), "unexpected template path: {path}");
+}
+
+#[test]
+fn json_envelope_meta_absent_on_error() {
+    let options = netcli_core::ParseOptions {
+        include_meta: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_command_json_with_options(
+        "cisco_ios",
+        "show magic unicorn",
+        "text",
+        &options,
+    ));
+
+    assert_eq!(v["ok"], false);
+    assert!(v.get("meta").is_none());
+}
+
+#[test]
+fn json_envelope_schema_version_is_opt_in() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let v = parse_envelope(&netcli_core::parse_json("cisco_ios", "show_version", output));
+    assert!(v.get("schemaVersion").is_none(), "schemaVersion is opt-in");
+
+    let options = netcli_core::ParseOptions {
+        include_schema_version: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options("cisco_ios", "show_version", output, &options));
+    assert_success(&v);
+    assert_eq!(v["schemaVersion"], netcli_core::ENVELOPE_SCHEMA_VERSION);
+
+    let v = parse_envelope(&netcli_core::parse_json_with_options("bad_os", "show_version", output, &options));
+    assert_eq!(v["ok"], false);
+    assert_eq!(v["schemaVersion"], netcli_core::ENVELOPE_SCHEMA_VERSION, "error envelopes carry it too");
+}
+
+#[test]
+fn json_envelope_list_fields_are_arrays() {
+    let output = include_str!("fixtures/cisco_ios/show_ipv6_interface_brief.txt");
+    let v = parse_envelope(&netcli_core::parse_command_json("cisco_ios", "show ipv6 interface brief", output));
+    assert_success(&v);
+
+    let recs = records(&v);
+    assert_eq!(
+        recs[0]["ipv6_address"],
+        serde_json::json!(["FE80::C801:1FF:FE2C:8", "2001:DB8:10::1"]),
+        "List values serialize as JSON arrays"
+    );
+    assert_eq!(recs[2]["ipv6_address"], serde_json::json!([]));
+    assert_eq!(recs[0]["interface"], "GigabitEthernet0/0", "scalar fields stay strings");
+}
+
+#[test]
+fn ndjson_is_one_record_object_per_line() {
+    let output = include_str!("fixtures/cisco_ios/show_ipv6_interface_brief.txt");
+    let ndjson = netcli_core::parse_records_ndjson("cisco_ios", "show_ipv6_interface_brief", output).unwrap();
+    assert_eq!(ndjson, netcli_core::parse_records_ndjson("cisco_ios", "show_ipv6_interface_brief", output).unwrap());
+    assert!(ndjson.ends_with('\n'));
+
+    let v = parse_envelope(&netcli_core::parse_json("cisco_ios", "show_ipv6_interface_brief", output));
+    let lines: Vec<&str> = ndjson.lines().collect();
+    assert_eq!(lines.len(), records(&v).len());
+    for (line, rec) in lines.iter().zip(records(&v)) {
+        assert!(line.starts_with('{') && !line.contains(": "), "compact object: {line}");
+        assert_eq!(&serde_json::from_str::<serde_json::Value>(line).unwrap(), rec);
+    }
+
+    let header = "Port      Name               Status       Reason               Err-disabled Vlans\n";
+    let empty = netcli_core::parse_records_ndjson("cisco_ios", "show_interfaces_status_err_disabled", header);
+    assert_eq!(empty.unwrap(), "");
+    let err = netcli_core::parse_records_ndjson("nonexistent_os", "show_version", output).unwrap_err();
+    assert_eq!(err.code(), "TEMPLATE_NOT_FOUND");
+}
+
+struct IosDerived;
+
+impl netcli_core::PlatformProvider for IosDerived {
+    fn slug(&self) -> &str {
+        "acme_ios"
+    }
+    fn aliases(&self) -> &[&str] {
+        &["acme"]
+    }
+    fn prompt_regex(&self) -> &str {
+        r"^acme-[\w.\-]+[>#]"
+    }
+    fn templates_from(&self) -> Option<&str> {
+        Some("cisco_ios")
+    }
+}
+
+#[test]
+fn registered_platform_parses_with_borrowed_templates() {
+    assert!(netcli_core::register_platform(Box::new(IosDerived)));
+
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+    let expected = netcli_core::parse_records("cisco_ios", "show_version", output).unwrap();
+    let recs = netcli_core::parse_command_records("acme", "show version", output).unwrap();
+    assert_eq!(recs, expected);
+
+    let v = parse_envelope(&netcli_core::parse_json("acme", "show_version", output));
+    assert_success(&v);
+    assert_eq!(v["platform"], "acme");
+    assert_eq!(netcli_core::resolve("acme", "show version").canonical_platform, "acme_ios");
+}
+
+#[test]
+fn runtime_platform_alias_reaches_records_and_command_apis() {
+    let output = include_str!("fixtures/arista_eos/show_version.txt");
+    let err = netcli_core::parse_records("arista_veos", "show_version", output).unwrap_err();
+    assert_eq!(err.code(), "TEMPLATE_NOT_FOUND");
+
+    assert!(netcli_core::register_platform_alias("arista_veos", "arista_eos"));
+    let expected = netcli_core::parse_records("arista_eos", "show_version", output).unwrap();
+    assert_eq!(netcli_core::parse_records("arista_veos", "show_version", output).unwrap(), expected);
+    assert_eq!(netcli_core::parse_command_records("arista_veos", "show version", output).unwrap(), expected);
+
+    assert!(netcli_core::register_platform_alias("cisco_ios_ssh", "cisco_ios"));
+    assert!(netcli_core::parse_records("cisco_ios_ssh", "show_version", output).is_ok());
+    assert!(!netcli_core::register_platform_alias("arista_eos", "cisco_ios"), "built-ins cannot be redirected");
+    assert!(netcli_core::parse_records("arista_eos", "show_version", output).is_ok());
+}
+
+#[test]
+fn json_envelope_record_keys_follow_template_order() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces.txt");
+    let options = netcli_core::ParseOptions::builder().normalize(true).build();
+    let first = netcli_core::parse_json_with_options("cisco_ios", "show_interfaces", output, &options);
+    let second = netcli_core::parse_json_with_options("cisco_ios", "show_interfaces", output, &options);
+    assert_eq!(first, second, "same input must serialize byte-for-byte the same");
+
+    // Check the raw text: parsing into a Value would re-sort the keys anyway.
+    let pos = |key: &str| first.find(&format!("\"{key}\":")).unwrap();
+    assert!(pos("interface") < pos("link_status"), "declared first");
+    assert!(pos("mac_address") < pos("bia"));
+    assert!(pos("mtu") < pos("speed"));
+    assert!(pos("total_output_drops") < pos("bandwidth_bps"), "derived fields come after declared ones");
+    assert!(pos("bandwidth_bps") < pos("load_interval_seconds"));
+    assert!(pos("ok") < pos("platform") && pos("platform") < pos("records"), "envelope keys stay sorted");
+
+    let v = parse_envelope(&first);
+    assert_eq!(v, parse_envelope(&netcli_core::parse_json_with_options(
+        "cisco_ios",
+        "show_interfaces",
+        output,
+        &netcli_core::ParseOptions::builder().normalize(true).sort_record_keys(true).build(),
+    )));
+}
+
+#[test]
+fn json_envelope_record_keys_are_sorted_on_request() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces.txt");
+    let options = netcli_core::ParseOptions::builder().sort_record_keys(true).build();
+    let first = netcli_core::parse_json_with_options("cisco_ios", "show_interfaces", output, &options);
+    let second = netcli_core::parse_json_with_options("cisco_ios", "show_interfaces", output, &options);
+    assert_eq!(first, second, "same input must serialize byte-for-byte the same");
+
+    let pos = |key: &str| first.find(&format!("\"{key}\":")).unwrap();
+    assert!(pos("bia") < pos("interface"));
+    assert!(pos("interface") < pos("mtu"));
+    assert!(pos("mtu") < pos("speed"));
 }
 
 #[test]
@@ -445,141 +1344,946 @@ fn json_envelope_error_shape() {
 }
 
 #[test]
-fn error_message_includes_context() {
-    let result = netcli_core::parse_records("my_platform", "my_command", "text");
-    let err = result.unwrap_err();
+fn error_message_includes_context() {
+    let result = netcli_core::parse_records("my_platform", "my_command", "text");
+    let err = result.unwrap_err();
+
+    let msg = err.to_string();
+    assert!(msg.contains("my_platform"), "error message should include platform: {msg}");
+    assert!(msg.contains("my_command"), "error message should include command key: {msg}");
+}
+
+#[test]
+fn parse_error_code_mapping() {
+    use netcli_core::ParseError;
+
+    assert_eq!(ParseError::InvalidInput("x").code(), "INVALID_INPUT");
+    assert_eq!(
+        ParseError::TemplateNotFound { platform: "a".into(), command_key: "b".into() }.code(),
+        "TEMPLATE_NOT_FOUND"
+    );
+    assert_eq!(ParseError::TemplateInvalid("x".into()).code(), "TEMPLATE_INVALID");
+    assert_eq!(ParseError::EngineError("x".into()).code(), "PARSE_ERROR");
+    assert_eq!(ParseError::UnexpectedRecordCount(2).code(), "UNEXPECTED_RECORD_COUNT");
+    assert_eq!(
+        ParseError::Deserialize { record: 0, field: None, message: "x".into() }.code(),
+        "DESERIALIZE_ERROR"
+    );
+}
+
+#[test]
+fn parse_error_codes_are_stable_and_non_empty() {
+    use netcli_core::ParseError;
+
+    let all = [
+        ParseError::InvalidInput("x"),
+        ParseError::TemplateNotFound { platform: "a".into(), command_key: "b".into() },
+        ParseError::TemplateInvalid("x".into()),
+        ParseError::EngineError("x".into()),
+        ParseError::UnexpectedRecordCount(2),
+        ParseError::Deserialize { record: 0, field: Some("f".into()), message: "x".into() },
+    ];
+    for e in &all {
+        assert!(!e.code().is_empty());
+        assert!(ParseError::CODES.contains(&e.code()), "{} missing from CODES", e.code());
+    }
+    assert_eq!(
+        ParseError::CODES,
+        [
+            "INVALID_INPUT",
+            "TEMPLATE_NOT_FOUND",
+            "TEMPLATE_INVALID",
+            "PARSE_ERROR",
+            "UNEXPECTED_RECORD_COUNT",
+            "DESERIALIZE_ERROR",
+        ],
+        "codes are a compat surface: append only"
+    );
+}
+
+#[test]
+fn parse_error_downstream_matching_via_code() {
+    // ParseError is #[non_exhaustive]: downstream matches need a catch-all,
+    // and branching on code() keeps working as variants are added.
+    let err = netcli_core::parse_records("nonexistent_os", "show_version", "x").unwrap_err();
+    let status = match err.code() {
+        "TEMPLATE_NOT_FOUND" => 404,
+        "INVALID_INPUT" => 400,
+        _ => 500,
+    };
+    assert_eq!(status, 404);
+
+    let described = match &err {
+        netcli_core::ParseError::TemplateNotFound { platform, .. } => platform.clone(),
+        _ => String::new(),
+    };
+    assert_eq!(described, "nonexistent_os");
+}
+
+#[test]
+fn parse_error_display_formatting() {
+    use netcli_core::ParseError;
+
+    let e1 = ParseError::InvalidInput("platform");
+    assert_eq!(e1.to_string(), "required input is empty: platform");
+
+    let e2 = ParseError::TemplateNotFound {
+        platform: "cisco_ios".into(),
+        command_key: "show_version".into(),
+    };
+    assert_eq!(e2.to_string(), "no template for (cisco_ios, show_version)");
+
+    let e3 = ParseError::TemplateInvalid("bad regex".into());
+    assert_eq!(e3.to_string(), "template compilation failed: bad regex");
+
+    let e4 = ParseError::EngineError("something broke".into());
+    assert_eq!(e4.to_string(), "parse error: something broke");
+}
+
+#[test]
+fn parse_error_implements_std_error() {
+    fn assert_std_error<T: std::error::Error>() {}
+    assert_std_error::<netcli_core::ParseError>();
+}
+
+#[test]
+fn malformed_template_is_told_apart_from_unmatched_input() {
+    let options = netcli_core::ParseOptions::default();
+    for template in ["Value FOO (\\d+\n\nStart\n  ^${FOO} -> Record\n", "Start\n  ^${MISSING} -> Record\n"] {
+        let err = netcli_core::run_template(template, "42\n", &options).unwrap_err();
+        assert_eq!(err.code(), "TEMPLATE_INVALID", "{template:?}");
+        assert!(err.to_string().starts_with("template compilation failed: "));
+    }
+
+    let recs = netcli_core::run_template("Value FOO (\\d+)\n\nStart\n  ^${FOO} -> Record\n", "no digits\n", &options);
+    assert!(recs.unwrap().is_empty(), "input the template does not match is not an error");
+}
+
+
+// --- cross-platform prompt tolerance ---
+
+#[test]
+fn arista_eos_show_version_tolerates_prompt() {
+    let clean = include_str!("fixtures/arista_eos/show_version.txt");
+    let with_prompt = format!("switch1#show version\n{clean}\nswitch1#");
+
+    let json_str = netcli_core::parse_json("arista_eos", "show_version", &with_prompt);
+    let v = parse_envelope(&json_str);
+
+    assert_success(&v);
+    let recs = records(&v);
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0]["model"], "DCS-7050TX-64");
+}
+
+#[test]
+fn cisco_nxos_show_version_tolerates_prompt() {
+    let clean = include_str!("fixtures/cisco_nxos/show_version.txt");
+    let with_prompt = format!("nxos-switch1# show version\n{clean}\nnxos-switch1#");
+
+    let json_str = netcli_core::parse_json("cisco_nxos", "show_version", &with_prompt);
+    let v = parse_envelope(&json_str);
+
+    assert_success(&v);
+    assert!(!records(&v).is_empty());
+}
+
+// --- consistency tests ---
+
+#[test]
+fn parse_json_and_parse_records_agree() {
+    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+
+    let json_str = netcli_core::parse_json("cisco_ios", "show_version", output);
+    let v = parse_envelope(&json_str);
+    let json_records = records(&v);
+
+    let direct_records = netcli_core::parse_records("cisco_ios", "show_version", output).unwrap();
+
+    assert_eq!(json_records.len(), direct_records.len());
+    for (jr, dr) in json_records.iter().zip(direct_records.iter()) {
+        for (key, value) in dr {
+            assert_eq!(jr[key], *value, "mismatch for key {key}");
+        }
+    }
+}
+
+#[test]
+fn command_and_key_apis_produce_identical_records_for_multiple_platforms() {
+    let platforms_and_commands: Vec<(&str, &str, &str)> = vec![
+        ("cisco_ios", "show_version", "show version"),
+        ("arista_eos", "show_version", "show version"),
+        ("cisco_nxos", "show_version", "show version"),
+    ];
+
+    for (platform, key, cmd) in platforms_and_commands {
+        let fixture = match (platform, key) {
+            ("cisco_ios", "show_version") => include_str!("fixtures/cisco_ios/show_version.txt"),
+            ("arista_eos", "show_version") => include_str!("fixtures/arista_eos/show_version.txt"),
+            ("cisco_nxos", "show_version") => include_str!("fixtures/cisco_nxos/show_version.txt"),
+            _ => unreachable!(),
+        };
+
+        let by_key = netcli_core::parse_records(platform, key, fixture).unwrap();
+        let by_cmd = netcli_core::parse_command_records(platform, cmd, fixture).unwrap();
+
+        assert_eq!(by_key.len(), by_cmd.len(), "record count mismatch for {platform}/{key}");
+        assert_eq!(by_key, by_cmd, "records differ for {platform}/{key}");
+    }
+}
+
+// ========================================================================
+// cisco_ios fixture tests
+// ========================================================================
+
+#[test]
+fn cisco_ios_show_ip_interface_brief() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_ip_interface_brief", output).unwrap();
+
+    assert_eq!(recs.len(), 12);
+    assert_eq!(recs[0].get("interface").unwrap(), "GigabitEthernet0/0");
+    assert_eq!(recs[0].get("ip_address").unwrap(), "10.1.1.1");
+    assert_eq!(recs[0].get("status").unwrap(), "up");
+    assert_eq!(recs[0].get("proto").unwrap(), "up");
+}
+
+#[test]
+fn cisco_ios_show_interfaces_status_err_disabled() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces_status_err_disabled.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show interfaces status err-disabled", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    assert_eq!(recs[0].get("interface").unwrap(), "Gi1/0/7");
+    assert_eq!(recs[0].get("name").unwrap(), "Desk-4B");
+    assert_eq!(recs[0].get("status").unwrap(), "err-disabled");
+    assert_eq!(recs[0].get("reason").unwrap(), "bpduguard");
+    assert_eq!(recs[1].get("interface").unwrap(), "Gi1/0/12");
+    assert_eq!(recs[1].get("name").unwrap(), "");
+    assert_eq!(recs[1].get("reason").unwrap(), "psecure-violation");
+}
+
+#[test]
+fn cisco_ios_show_ipv6_interface_brief() {
+    let output = include_str!("fixtures/cisco_ios/show_ipv6_interface_brief.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show ipv6 interface brief", output).unwrap();
+
+    assert_eq!(recs.len(), 4);
+    assert_eq!(recs[0].get("interface").unwrap(), "GigabitEthernet0/0");
+    assert_eq!(recs[0].get("admin").unwrap(), "up");
+    assert_eq!(recs[0].get("protocol").unwrap(), "up");
+    assert_eq!(
+        recs[0].get("ipv6_address").unwrap(),
+        "[FE80::C801:1FF:FE2C:8, 2001:DB8:10::1]",
+        "link-local and global address should both be collected"
+    );
+    assert_eq!(recs[2].get("admin").unwrap(), "administratively down");
+    assert_eq!(recs[2].get("ipv6_address").unwrap(), "[]");
+}
+
+#[test]
+fn cisco_ios_show_ipv6_route() {
+    let output = include_str!("fixtures/cisco_ios/show_ipv6_route.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show ipv6 route", output).unwrap();
+
+    assert_eq!(recs.len(), 6);
+    assert_eq!(recs[0].get("protocol").unwrap(), "S");
+    assert_eq!(recs[0].get("network").unwrap(), "::/0");
+    assert_eq!(recs[0].get("nexthop_ip").unwrap(), "2001:DB8:10::FFFF");
+    assert_eq!(recs[4].get("protocol").unwrap(), "O");
+    assert_eq!(recs[4].get("distance").unwrap(), "110");
+    assert_eq!(recs[4].get("metric").unwrap(), "20");
+    assert_eq!(recs[4].get("nexthop_ip").unwrap(), "FE80::C802:1FF:FE2C:8");
+    assert_eq!(recs[4].get("nexthop_if").unwrap(), "GigabitEthernet0/0");
+}
+
+#[test]
+fn cisco_ios_show_authentication_sessions() {
+    let output = include_str!("fixtures/cisco_ios/show_authentication_sessions.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show authentication sessions", output).unwrap();
+
+    assert_eq!(recs.len(), 5);
+    assert_eq!(recs[0].get("interface").unwrap(), "Gi1/0/2");
+    assert_eq!(recs[0].get("mac_address").unwrap(), "0050.5682.1a2b");
+    assert_eq!(recs[0].get("method").unwrap(), "dot1x");
+    assert_eq!(recs[0].get("domain").unwrap(), "DATA");
+    assert_eq!(recs[0].get("status").unwrap(), "Auth");
+    assert_eq!(recs[0].get("session_id").unwrap(), "0A0A0A01000000121A2B3C4D");
+    assert_eq!(recs[2].get("status").unwrap(), "Unauth");
+    assert_eq!(recs[3].get("status").unwrap(), "Authz Success", "older IOS prints a two-word status");
+    assert_eq!(recs[3].get("session_id").unwrap(), "0A0A0A01000000171A2B3C7A");
+    assert_eq!(recs[4].get("status").unwrap(), "Authz Failed");
+}
+
+#[test]
+fn cisco_ios_show_authentication_sessions_interface_details() {
+    let output = include_str!("fixtures/cisco_ios/show_authentication_sessions_interface_details.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_authentication_sessions", output).unwrap();
+
+    assert_eq!(recs.len(), 2);
+    assert_eq!(recs[0].get("interface").unwrap(), "GigabitEthernet1/0/2");
+    assert_eq!(recs[0].get("mac_address").unwrap(), "0050.5682.1a2b");
+    assert_eq!(recs[0].get("method").unwrap(), "dot1x");
+    assert_eq!(recs[0].get("status").unwrap(), "Authorized");
+    assert_eq!(recs[0].get("session_id").unwrap(), "0A0A0A01000000121A2B3C4D");
+    assert_eq!(recs[1].get("method").unwrap(), "mab");
+    assert_eq!(recs[1].get("domain").unwrap(), "VOICE");
+}
+
+#[test]
+fn cisco_ios_show_ip_igmp_snooping_groups() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_igmp_snooping_groups.txt");
+    let recs =
+        netcli_core::parse_command_records("cisco_ios", "show ip igmp snooping groups", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    assert_eq!(recs[0].get("vlan").unwrap(), "10");
+    assert_eq!(recs[0].get("group_address").unwrap(), "239.1.1.1");
+    assert_eq!(recs[0].get("type").unwrap(), "igmp");
+    assert_eq!(recs[0].get("version").unwrap(), "v2");
+    assert_eq!(
+        recs[0].get("port_list").unwrap(),
+        "[Gi1/0/1, Gi1/0/2, Gi1/0/3, Gi1/0/4, Gi1/0/11]",
+        "ports on wrapped continuation lines belong to the same group"
+    );
+    assert_eq!(recs[1].get("port_list").unwrap(), "[Gi1/0/7]");
+    assert_eq!(recs[2].get("vlan").unwrap(), "20");
+    assert_eq!(recs[2].get("port_list").unwrap(), "[Gi1/0/5, Po1]");
+}
+
+#[test]
+fn cisco_ios_show_clock_not_authoritative() {
+    let output = include_str!("fixtures/cisco_ios/show_clock.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show clock", output).unwrap();
+
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("sync_state").unwrap(), "*");
+    assert_eq!(recs[0].get("time").unwrap(), "10:15:32.123");
+    assert_eq!(recs[0].get("timezone").unwrap(), "UTC");
+    assert_eq!(recs[0].get("sync_source").unwrap(), "");
+}
+
+#[test]
+fn cisco_ios_show_clock_detail_with_sync_source() {
+    let output = include_str!("fixtures/cisco_ios/show_clock_detail.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show clock detail", output).unwrap();
+
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("sync_state").unwrap(), ".");
+    assert_eq!(recs[0].get("time").unwrap(), "14:02:11.907");
+    assert_eq!(recs[0].get("timezone").unwrap(), "PST");
+    assert_eq!(recs[0].get("sync_source").unwrap(), "NTP");
+}
+
+#[test]
+fn cisco_ios_show_vrf_with_wrapped_interfaces() {
+    let output = include_str!("fixtures/cisco_ios/show_vrf.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show vrf", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    assert_eq!(recs[0].get("vrf_name").unwrap(), "CUST_A");
+    assert_eq!(recs[0].get("rd").unwrap(), "65000:100");
+    assert_eq!(recs[0].get("protocols").unwrap(), "ipv4");
+    assert_eq!(recs[0].get("interfaces").unwrap(), "[Gi0/1, Gi0/2.100]");
+    assert_eq!(recs[1].get("rd").unwrap(), "<not set>");
+    assert_eq!(recs[2].get("vrf_name").unwrap(), "VRF_EMPTY");
+    assert_eq!(recs[2].get("interfaces").unwrap(), "[]");
+}
+
+#[test]
+fn cisco_nxos_show_vrf_interface() {
+    let output = include_str!("fixtures/cisco_nxos/show_vrf_interface.txt");
+    let recs = netcli_core::parse_command_records("cisco_nxos", "show vrf interface", output).unwrap();
+
+    let members: Vec<&str> = recs
+        .iter()
+        .filter(|r| r.get("vrf_name").map(String::as_str) == Some("CUST_A"))
+        .map(|r| r.get("interface").unwrap().as_str())
+        .collect();
+    assert_eq!(recs.len(), 4);
+    assert_eq!(members, ["Ethernet1/1", "Ethernet1/2"]);
+    assert_eq!(recs[3].get("vrf_id").unwrap(), "2");
+}
+
+#[test]
+fn cisco_nxos_show_module_supervisors_and_linecards() {
+    let output = include_str!("fixtures/cisco_nxos/show_module.txt");
+    let recs = netcli_core::parse_command_records("cisco_nxos", "show module", output).unwrap();
+
+    assert_eq!(recs.len(), 4, "serial rows merge into their module; xbars are skipped");
+
+    let linecard = &recs[0];
+    assert_eq!(linecard.get("module").unwrap(), "1");
+    assert_eq!(linecard.get("ports").unwrap(), "48");
+    assert_eq!(linecard.get("module_type").unwrap(), "1/10 Gbps Ethernet Module");
+    assert_eq!(linecard.get("model").unwrap(), "N7K-F248XP-25E");
+    assert_eq!(linecard.get("status").unwrap(), "ok");
+    assert_eq!(linecard.get("serial").unwrap(), "JAF1717AAHP");
+
+    let active = &recs[2];
+    assert_eq!(active.get("module").unwrap(), "5");
+    assert_eq!(active.get("module_type").unwrap(), "Supervisor Module-2");
+    assert_eq!(active.get("status").unwrap(), "active *");
+    assert_eq!(active.get("serial").unwrap(), "JAF1812ARBG");
+    assert_eq!(recs[3].get("status").unwrap(), "ha-standby");
+}
+
+#[test]
+fn cisco_nxos_show_system_resources() {
+    let output = include_str!("fixtures/cisco_nxos/show_system_resources.txt");
+    let rec = netcli_core::parse_single("cisco_nxos", "show_system_resources", output)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(rec.get("cpu_user").unwrap(), "1.81");
+    assert_eq!(rec.get("cpu_kernel").unwrap(), "1.23");
+    assert_eq!(rec.get("cpu_idle").unwrap(), "96.95", "aggregate line, not a per-core one");
+    assert_eq!(rec.get("load_avg_1m").unwrap(), "0.34");
+    assert_eq!(rec.get("memory_total").unwrap(), "16399804");
+    assert_eq!(rec.get("memory_used").unwrap(), "5868392");
+    assert_eq!(rec.get("memory_free").unwrap(), "10531412");
+}
+
+#[test]
+fn cisco_nxos_show_bgp_l2vpn_evpn() {
+    let output = include_str!("fixtures/cisco_nxos/show_bgp_l2vpn_evpn.txt");
+    let recs = netcli_core::parse_command_records("cisco_nxos", "show bgp l2vpn evpn", output).unwrap();
+
+    assert_eq!(recs.len(), 9, "one record per path");
+    let mac_ip = &recs[1];
+    assert_eq!(mac_ip.get("rd").unwrap(), "10.0.0.11:32867");
+    assert_eq!(mac_ip.get("route_type").unwrap(), "2");
+    assert_eq!(mac_ip.get("mac_address").unwrap(), "0050.5600.0b02");
+    assert_eq!(mac_ip.get("ip_address").unwrap(), "192.168.10.20");
+    assert_eq!(mac_ip.get("next_hop").unwrap(), "10.0.0.102");
+    assert_eq!(mac_ip.get("esi").unwrap(), "0");
+    assert_eq!(recs[0].get("ip_address").unwrap(), "", "MAC-only route");
+
+    let imet = &recs[2];
+    assert_eq!(imet.get("route_type").unwrap(), "3");
+    assert_eq!(imet.get("mac_address").unwrap(), "", "route fields reset on each route");
+    assert_eq!(imet.get("ip_address").unwrap(), "10.0.0.101");
+
+    assert_eq!(recs[4].get("rd").unwrap(), "10.0.0.12:32867", "RD fills down");
+    assert_eq!(recs[4].get("status").unwrap(), "*", "second path to the same route");
+    assert_eq!(recs[4].get("mac_address").unwrap(), "0050.5600.0b02");
+    assert_eq!(recs[6].get("esi").unwrap(), "0300.0000.0000.0c00.0309");
+    assert_eq!(recs[8].get("route_type").unwrap(), "5");
+    assert_eq!(recs[8].get("ip_address").unwrap(), "192.168.50.0");
+}
+
+#[test]
+fn arista_eos_show_bgp_evpn() {
+    let output = include_str!("fixtures/arista_eos/show_bgp_evpn.txt");
+    let recs = netcli_core::parse_command_records("arista_eos", "show bgp evpn", output).unwrap();
+    assert_eq!(recs, netcli_core::parse_records("arista_eos", "show_bgp_l2vpn_evpn", output).unwrap());
+
+    assert_eq!(recs.len(), 7);
+    let mac_ip = &recs[1];
+    assert_eq!(mac_ip.get("rd").unwrap(), "10.0.0.2:10100");
+    assert_eq!(mac_ip.get("route_type").unwrap(), "mac-ip");
+    assert_eq!(mac_ip.get("mac_address").unwrap(), "0050.5600.0b02");
+    assert_eq!(mac_ip.get("ip_address").unwrap(), "192.168.10.20");
+    assert_eq!(mac_ip.get("next_hop").unwrap(), "10.0.0.2");
+    assert_eq!(recs[0].get("next_hop").unwrap(), "-", "locally originated");
+    assert_eq!(recs[3].get("status").unwrap(), "*  ec");
+    assert_eq!(recs[4].get("esi").unwrap(), "0000:0000:0000:0000:0001");
+    assert_eq!(recs[6].get("ip_address").unwrap(), "192.168.50.0/24");
+}
+
+#[test]
+fn cisco_ios_show_isis_neighbors() {
+    let output = include_str!("fixtures/cisco_ios/show_isis_neighbors.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show isis neighbors", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    let pe2 = &recs[0];
+    assert_eq!(pe2.get("tag").unwrap(), "CORE");
+    assert_eq!(pe2.get("system_id").unwrap(), "pe2");
+    assert_eq!(pe2.get("interface").unwrap(), "Gi0/0/1");
+    assert_eq!(pe2.get("state").unwrap(), "UP");
+    assert_eq!(pe2.get("type").unwrap(), "L2");
+    assert_eq!(pe2.get("hold_time").unwrap(), "26");
+    assert_eq!(pe2.get("circuit_id").unwrap(), "pe2.01");
+    assert_eq!(recs[2].get("state").unwrap(), "INIT");
+}
+
+#[test]
+fn cisco_ios_show_interfaces_capabilities() {
+    let output = include_str!("fixtures/cisco_ios/show_interfaces_capabilities.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show interfaces capabilities", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    let gi1 = &recs[0];
+    assert_eq!(gi1.get("interface").unwrap(), "GigabitEthernet1/0/1");
+    assert_eq!(gi1.get("model").unwrap(), "WS-C3850-48P");
+    assert_eq!(gi1.get("type").unwrap(), "10/100/1000BaseTX");
+    let speeds: Vec<&str> = gi1.get("speed").unwrap().split(',').collect();
+    assert_eq!(speeds, ["10", "100", "1000", "auto"]);
+    assert_eq!(gi1.get("duplex").unwrap(), "half,full,auto");
+    assert_eq!(gi1.get("flowcontrol").unwrap(), "rx-(off,on,desired),tx-(none)");
+
+    let te = &recs[2];
+    assert_eq!(te.get("interface").unwrap(), "TenGigabitEthernet1/1/1");
+    assert_eq!(te.get("speed").unwrap(), "1000,10000");
+    assert_eq!(te.get("duplex").unwrap(), "full");
+}
+
+#[test]
+fn cisco_ios_show_processes_memory() {
+    let output = include_str!("fixtures/cisco_ios/show_processes_memory.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show processes memory", output).unwrap();
+
+    assert_eq!(recs.len(), 9, "footer Total line is not a process");
+    let top = recs
+        .iter()
+        .max_by_key(|r| r.get("holding").unwrap().parse::<u64>().unwrap())
+        .unwrap();
+    assert_eq!(top.get("process").unwrap(), "*Init*");
+    assert_eq!(top.get("holding").unwrap(), "153718544");
+
+    let rib = recs.iter().find(|r| r.get("pid").unwrap() == "112").unwrap();
+    assert_eq!(rib.get("process").unwrap(), "IP RIB Update");
+    assert_eq!(rib.get("allocated").unwrap(), "48121840");
+    assert_eq!(rib.get("freed").unwrap(), "11003456");
+
+    for rec in &recs {
+        assert_eq!(rec.get("memory_total").unwrap(), "766710196");
+        assert_eq!(rec.get("memory_used").unwrap(), "207418576", "processor pool, not lsmpi_io");
+        assert_eq!(rec.get("memory_free").unwrap(), "559291620");
+    }
+}
+
+#[test]
+fn cisco_ios_show_vtp_status() {
+    let output = include_str!("fixtures/cisco_ios/show_vtp_status.txt");
+    let rec = netcli_core::parse_single("cisco_ios", "show_vtp_status", output)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(rec.get("domain").unwrap(), "CAMPUS-CORE");
+    assert_eq!(rec.get("mode").unwrap(), "Server");
+    assert_eq!(rec.get("version").unwrap(), "1");
+    assert_eq!(rec.get("max_vlans").unwrap(), "1005");
+    assert_eq!(rec.get("existing_vlans").unwrap(), "34");
+    assert_eq!(rec.get("configuration_revision").unwrap(), "42");
+    assert_eq!(rec.get("pruning").unwrap(), "Enabled");
+}
+
+#[test]
+fn cisco_ios_show_vtp_status_v3_keeps_vlan_feature() {
+    let output = include_str!("fixtures/cisco_ios/show_vtp_status_v3.txt");
+    let rec = netcli_core::parse_single("cisco_ios", "show_vtp_status", output)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(rec.get("domain").unwrap(), "DC-EDGE");
+    assert_eq!(rec.get("mode").unwrap(), "Primary Server", "MST/UNKNOWN feature modes are ignored");
+    assert_eq!(rec.get("version").unwrap(), "3");
+    assert_eq!(rec.get("configuration_revision").unwrap(), "7");
+}
+
+#[test]
+fn cisco_ios_show_ip_eigrp_topology_rows_per_path() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_eigrp_topology.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show ip eigrp topology", output).unwrap();
+
+    assert_eq!(recs.len(), 7);
+    assert!(recs.iter().all(|r| r.get("process_id").unwrap() == "100"));
+
+    let paths: Vec<_> = recs.iter().filter(|r| r.get("prefix").unwrap() == "10.2.2.0/24").collect();
+    assert_eq!(paths.len(), 2);
+    for (path, next_hop) in paths.iter().zip(["10.0.12.2", "10.0.13.3"]) {
+        assert_eq!(path.get("successors").unwrap(), "2");
+        assert_eq!(path.get("fd").unwrap(), "3072");
+        assert_eq!(path.get("network").unwrap(), "10.2.2.0");
+        assert_eq!(path.get("prefix_length").unwrap(), "24");
+        assert_eq!(path.get("next_hop").unwrap(), next_hop);
+        assert_eq!(path.get("reported_distance").unwrap(), "2816");
+    }
+
+    assert_eq!(recs[0].get("next_hop").unwrap(), "Connected");
+    assert_eq!(recs[0].get("out_interface").unwrap(), "GigabitEthernet0/0");
+    assert_eq!(recs[4].get("tag").unwrap(), "200");
+    assert_eq!(recs[5].get("tag").unwrap(), "", "tag must not fill down across prefixes");
+    assert_eq!(recs[6].get("fd").unwrap(), "Inaccessible");
+}
+
+#[test]
+fn cisco_ios_show_ip_bgp_prefix_table() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_bgp.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show ip bgp", output).unwrap();
+
+    assert_eq!(recs.len(), 6);
+
+    let best: Vec<_> = recs
+        .iter()
+        .filter(|r| r.get("network").unwrap() == "172.16.0.0/16")
+        .collect();
+    assert_eq!(best.len(), 2, "second next hop fills down the network");
+    assert_eq!(best[0].get("status_codes").unwrap(), "*");
+    assert_eq!(best[1].get("status_codes").unwrap(), "*>");
+    assert_eq!(best[1].get("next_hop").unwrap(), "192.168.1.1");
+    assert_eq!(best[1].get("metric").unwrap(), "0");
+    assert_eq!(best[1].get("weight").unwrap(), "0");
+    assert_eq!(best[1].get("path").unwrap(), "65002 65010");
+    assert_eq!(best[1].get("as_path").unwrap(), "65002 65010", "original field name still filled");
+
+    assert_eq!(recs[4].get("status_codes").unwrap(), "*>i");
+    assert_eq!(recs[4].get("local_pref").unwrap(), "100");
+    assert_eq!(recs[5].get("network").unwrap(), "192.168.100.0/24");
+    assert_eq!(recs[5].get("origin").unwrap(), "?");
+}
+
+#[test]
+fn cisco_ios_show_users_active_ssh_session() {
+    let output = include_str!("fixtures/cisco_ios/show_users.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show users", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
+    assert_eq!(recs[0].get("line").unwrap(), "0 con 0");
+    assert_eq!(recs[0].get("user").unwrap(), "", "console has no user logged in");
+    assert_eq!(recs[0].get("hosts").unwrap(), "idle");
+
+    let active = &recs[1];
+    assert_eq!(active.get("line").unwrap(), "*  2 vty 0");
+    assert_eq!(active.get("user").unwrap(), "admin");
+    assert_eq!(active.get("idle").unwrap(), "00:00:00");
+    assert_eq!(active.get("location").unwrap(), "10.1.1.50");
+}
+
+#[test]
+fn cisco_ios_show_aaa_sessions() {
+    let output = include_str!("fixtures/cisco_ios/show_aaa_sessions.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show aaa sessions", output).unwrap();
+
+    assert_eq!(recs.len(), 2);
+    assert_eq!(recs[0].get("account_name").unwrap(), "*not available*");
+    assert_eq!(recs[1].get("session_id").unwrap(), "11");
+    assert_eq!(recs[1].get("unique_id").unwrap(), "342");
+    assert_eq!(recs[1].get("account_name").unwrap(), "admin");
+    assert_eq!(recs[1].get("ip_address").unwrap(), "10.1.1.50");
+}
+
+#[test]
+fn cisco_ios_show_logging_severity_extraction() {
+    let output = include_str!("fixtures/cisco_ios/show_logging.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show logging", output).unwrap();
 
-    let msg = err.to_string();
-    assert!(msg.contains("my_platform"), "error message should include platform: {msg}");
-    assert!(msg.contains("my_command"), "error message should include command key: {msg}");
+    assert_eq!(recs.len(), 4, "buffer-stats header must not produce records");
+
+    let link = &recs[1];
+    assert_eq!(link.get("timestamp").unwrap(), "Mar  1 09:14:02.551 UTC");
+    assert_eq!(link.get("facility").unwrap(), "LINK");
+    assert_eq!(link.get("severity").unwrap(), "3");
+    assert_eq!(link.get("mnemonic").unwrap(), "UPDOWN");
+    assert_eq!(
+        link.get("message").unwrap(),
+        "[Interface GigabitEthernet0/1, changed state to down]"
+    );
+    assert_eq!(recs[3].get("facility").unwrap(), "SEC_LOGIN");
 }
 
 #[test]
-fn parse_error_code_mapping() {
-    use netcli_core::ParseError;
+fn cisco_ios_show_logging_envelope_keeps_list_items_whole() {
+    let output = include_str!("fixtures/cisco_ios/show_logging.txt");
+    let v = parse_envelope(&netcli_core::parse_json("cisco_ios", "show_logging", output));
 
-    assert_eq!(ParseError::InvalidInput("x").code(), "INVALID_INPUT");
+    assert_success(&v);
+    let recs = records(&v);
     assert_eq!(
-        ParseError::TemplateNotFound { platform: "a".into(), command_key: "b".into() }.code(),
-        "TEMPLATE_NOT_FOUND"
+        recs[1]["message"],
+        serde_json::json!(["Interface GigabitEthernet0/1, changed state to down"]),
+        "an item containing `, ` stays one item"
     );
-    assert_eq!(ParseError::TemplateInvalid("x".into()).code(), "TEMPLATE_INVALID");
-    assert_eq!(ParseError::EngineError("x".into()).code(), "PARSE_ERROR");
+
+    let csv = netcli_core::parse_records_csv("cisco_ios", "show_logging", output).unwrap();
+    assert!(csv.contains(",\"Interface GigabitEthernet0/1, changed state to down\"\r\n"), "{csv}");
 }
 
 #[test]
-fn parse_error_display_formatting() {
-    use netcli_core::ParseError;
+fn cisco_ios_show_policy_map_interface_class_counters() {
+    let output = include_str!("fixtures/cisco_ios/show_policy_map_interface.txt");
+    let recs =
+        netcli_core::parse_command_records("cisco_ios", "show policy-map interface", output).unwrap();
 
-    let e1 = ParseError::InvalidInput("platform");
-    assert_eq!(e1.to_string(), "required input is empty: platform");
+    assert_eq!(recs.len(), 5, "priority queue stats must not become a class");
 
-    let e2 = ParseError::TemplateNotFound {
-        platform: "cisco_ios".into(),
-        command_key: "show_version".into(),
-    };
-    assert_eq!(e2.to_string(), "no template for (cisco_ios, show_version)");
+    let scavenger = &recs[0];
+    assert_eq!(scavenger.get("interface").unwrap(), "GigabitEthernet0/1");
+    assert_eq!(scavenger.get("direction").unwrap(), "input");
+    assert_eq!(scavenger.get("policy_name").unwrap(), "MARK-IN");
+    assert_eq!(scavenger.get("class_map").unwrap(), "SCAVENGER");
+    assert_eq!(scavenger.get("exceeded_packets").unwrap(), "1024");
+    assert_eq!(scavenger.get("exceeded_bytes").unwrap(), "1536000");
+    assert_eq!(scavenger.get("exceed_action").unwrap(), "drop");
+    assert_eq!(scavenger.get("offered_bytes").unwrap(), "7680000", "class totals, not per-match counters");
 
-    let e3 = ParseError::TemplateInvalid("bad regex".into());
-    assert_eq!(e3.to_string(), "template compilation failed: bad regex");
+    let default_out = &recs[3];
+    assert_eq!(default_out.get("direction").unwrap(), "output");
+    assert_eq!(default_out.get("class_map").unwrap(), "class-default");
+    assert_eq!(default_out.get("queue_drops").unwrap(), "312");
+    assert_eq!(default_out.get("transmitted_bytes").unwrap(), "123001234");
 
-    let e4 = ParseError::EngineError("something broke".into());
-    assert_eq!(e4.to_string(), "parse error: something broke");
+    assert_eq!(recs[2].get("match_criteria").unwrap(), "[ip dscp ef (46), protocol rtp]");
+    assert_eq!(recs[4].get("interface").unwrap(), "GigabitEthernet0/2");
+    assert_eq!(recs[4].get("policy_name").unwrap(), "WAN-EDGE");
 }
 
 #[test]
-fn parse_error_implements_std_error() {
-    fn assert_std_error<T: std::error::Error>() {}
-    assert_std_error::<netcli_core::ParseError>();
+fn cisco_ios_show_bgp_vpnv4_unicast_all_route_distinguishers() {
+    let output = include_str!("fixtures/cisco_ios/show_bgp_vpnv4_unicast_all.txt");
+    let recs =
+        netcli_core::parse_command_records("cisco_ios", "show bgp vpnv4 unicast all", output).unwrap();
+
+    assert_eq!(recs.len(), 6);
+
+    let cust_a: Vec<_> = recs
+        .iter()
+        .filter(|r| r.get("route_distinguisher").unwrap() == "65000:100")
+        .collect();
+    assert_eq!(cust_a.len(), 2);
+    assert_eq!(cust_a[0].get("vrf").unwrap(), "CUST_A");
+    assert_eq!(cust_a[0].get("network").unwrap(), "10.10.1.0/24");
+    assert_eq!(cust_a[0].get("next_hop").unwrap(), "0.0.0.0");
+    assert_eq!(cust_a[0].get("weight").unwrap(), "32768");
+    assert_eq!(cust_a[1].get("local_pref").unwrap(), "100");
+
+    let cust_b: Vec<_> = recs
+        .iter()
+        .filter(|r| r.get("route_distinguisher").unwrap() == "65000:200")
+        .collect();
+    assert_eq!(cust_b.len(), 3);
+    assert_eq!(cust_b[0].get("network").unwrap(), "172.31.0.0/16");
+    assert_eq!(cust_b[0].get("metric").unwrap(), "0");
+    assert_eq!(cust_b[0].get("path").unwrap(), "65100");
+    assert_eq!(cust_b[1].get("network").unwrap(), "172.31.0.0/16", "second path fills down the network");
+    assert_eq!(cust_b[1].get("next_hop").unwrap(), "192.168.50.6");
+    assert_eq!(cust_b[1].get("path").unwrap(), "65100 65100");
+
+    assert_eq!(recs[5].get("route_distinguisher").unwrap(), "65000:999");
+    assert_eq!(recs[5].get("vrf").unwrap(), "", "RD without a default VRF must not inherit one");
+    assert_eq!(recs[5].get("metric").unwrap(), "");
+}
+
+#[test]
+fn cisco_ios_show_environment_temperature_sensor_states() {
+    let output = include_str!("fixtures/cisco_ios/show_environment_temperature.txt");
+    let options = netcli_core::ParseOptions {
+        normalize: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_command_json_with_options(
+        "cisco_ios",
+        "show environment temperature",
+        output,
+        &options,
+    ));
+
+    assert_success(&v);
+    let recs = records(&v);
+    assert_eq!(recs.len(), 6);
+
+    let inlet = &recs[0];
+    assert_eq!(inlet["module"], "1");
+    assert_eq!(inlet["sensor"], "air inlet");
+    assert_eq!(inlet["reading_celsius"], "24");
+    assert_eq!(inlet["threshold"], "51");
+    assert_eq!(inlet["temperature_state"], "ok");
+
+    let cpu = &recs[3];
+    assert_eq!(cpu["sensor"], "CPU");
+    assert_eq!(cpu["reading_celsius"], "81");
+    assert_eq!(cpu["threshold"], "75");
+    assert_eq!(cpu["state"], "warning");
+    assert_eq!(cpu["temperature_state"], "warning");
+
+    assert_eq!(recs[4]["temperature_state"], "critical");
+    assert_eq!(recs[5]["module"], "PS1");
 }
 
+#[test]
+fn cisco_ios_show_spanning_tree_detail_topology_changes() {
+    let output = include_str!("fixtures/cisco_ios/show_spanning_tree_detail.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show spanning-tree detail", output).unwrap();
 
-// --- cross-platform prompt tolerance ---
+    assert_eq!(recs.len(), 3, "one record per port, VLAN fields filled down");
+
+    let vlan1: Vec<_> = recs.iter().filter(|r| r.get("vlan").unwrap() == "1").collect();
+    assert_eq!(vlan1.len(), 2);
+    assert!(vlan1.iter().all(|r| r.get("topology_changes").unwrap() == "12"));
+    assert_eq!(vlan1[0].get("last_topology_change").unwrap(), "2d03h");
+    assert_eq!(vlan1[0].get("root_id").unwrap(), "0019.e7ff.1200");
+    assert_eq!(vlan1[0].get("bridge_id").unwrap(), "0019.e8a5.3b80");
+    assert_eq!(vlan1[1].get("interface").unwrap(), "GigabitEthernet1/0/49");
+    assert_eq!(vlan1[1].get("role").unwrap(), "root");
+    assert_eq!(vlan1[1].get("designated_bridge").unwrap(), "0019.e7ff.1200");
+
+    let vlan10 = &recs[2];
+    assert_eq!(vlan10.get("vlan").unwrap(), "10");
+    assert_eq!(vlan10.get("topology_changes").unwrap(), "3");
+    assert_eq!(vlan10.get("root_id").unwrap(), vlan10.get("bridge_id").unwrap(), "we are the root");
+    assert_eq!(vlan10.get("designated_bridge_priority").unwrap(), "4106");
+}
 
 #[test]
-fn arista_eos_show_version_tolerates_prompt() {
-    let clean = include_str!("fixtures/arista_eos/show_version.txt");
-    let with_prompt = format!("switch1#show version\n{clean}\nswitch1#");
+fn cisco_ios_show_ip_route_vrf_carries_vrf() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_route_vrf.txt");
+    let recs = netcli_core::parse_records("cisco_ios", "show_ip_route_vrf", output).unwrap();
 
-    let json_str = netcli_core::parse_json("arista_eos", "show_version", &with_prompt);
-    let v = parse_envelope(&json_str);
+    assert_eq!(recs.len(), 6);
+    assert!(recs.iter().all(|r| r.get("vrf").unwrap() == "CUST_A"));
 
-    assert_success(&v);
-    let recs = records(&v);
-    assert_eq!(recs.len(), 1);
-    assert_eq!(recs[0]["model"], "DCS-7050TX-64");
+    assert_eq!(recs[0].get("flag").unwrap(), "*");
+    assert_eq!(recs[0].get("nexthop_ip").unwrap(), "10.20.0.1");
+    assert_eq!(recs[3].get("protocol").unwrap(), "B");
+    assert_eq!(recs[3].get("network").unwrap(), "10.30.1.0");
+    assert_eq!(recs[3].get("nexthop_ip").unwrap(), "192.0.2.11");
+    assert_eq!(recs[5].get("prefix_length").unwrap(), "24", "mask from the subnetted line");
+    assert_eq!(recs[5].get("nexthop_if").unwrap(), "GigabitEthernet0/1.100");
 }
 
 #[test]
-fn cisco_nxos_show_version_tolerates_prompt() {
-    let clean = include_str!("fixtures/cisco_nxos/show_version.txt");
-    let with_prompt = format!("nxos-switch1# show version\n{clean}\nnxos-switch1#");
+fn cisco_ios_show_ip_ospf_database_lsa_sections() {
+    let output = include_str!("fixtures/cisco_ios/show_ip_ospf_database.txt");
+    let recs = netcli_core::parse_command_records("cisco_ios", "show ip ospf database", output).unwrap();
 
-    let json_str = netcli_core::parse_json("cisco_nxos", "show_version", &with_prompt);
-    let v = parse_envelope(&json_str);
+    assert_eq!(recs.len(), 8);
+    assert!(recs.iter().all(|r| r.get("router_id").unwrap() == "1.1.1.1"));
 
-    assert_success(&v);
-    assert!(!records(&v).is_empty());
+    let router = &recs[1];
+    assert_eq!(router.get("lsa_type").unwrap(), "Router");
+    assert_eq!(router.get("area").unwrap(), "0");
+    assert_eq!(router.get("link_id").unwrap(), "2.2.2.2");
+    assert_eq!(router.get("adv_router").unwrap(), "2.2.2.2");
+    assert_eq!(router.get("age").unwrap(), "389");
+    assert_eq!(router.get("seq").unwrap(), "0x80000004");
+    assert_eq!(router.get("checksum").unwrap(), "0x003C4D");
+    assert_eq!(router.get("link_count").unwrap(), "2");
+
+    let network = &recs[2];
+    assert_eq!(network.get("lsa_type").unwrap(), "Net");
+    assert_eq!(network.get("link_id").unwrap(), "10.0.12.2");
+    assert_eq!(network.get("adv_router").unwrap(), "2.2.2.2");
+    assert_eq!(network.get("link_count").unwrap(), "");
+
+    assert_eq!(recs[5].get("area").unwrap(), "10");
+    assert_eq!(recs[5].get("adv_router").unwrap(), "3.3.3.3");
+
+    let external = &recs[7];
+    assert_eq!(external.get("lsa_type").unwrap(), "Type-5 AS External");
+    assert_eq!(external.get("area").unwrap(), "", "AS-scoped LSAs belong to no area");
+    assert_eq!(external.get("tag").unwrap(), "100");
 }
 
-// --- consistency tests ---
+// ========================================================================
+// cisco_xr fixture tests
+// ========================================================================
 
 #[test]
-fn parse_json_and_parse_records_agree() {
-    let output = include_str!("fixtures/cisco_ios/show_version.txt");
+fn cisco_xr_show_bgp_vpnv4_unicast_route_distinguishers() {
+    let output = include_str!("fixtures/cisco_xr/show_bgp_vpnv4_unicast.txt");
+    let recs =
+        netcli_core::parse_command_records("cisco_xr", "show bgp vpnv4 unicast", output).unwrap();
 
-    let json_str = netcli_core::parse_json("cisco_ios", "show_version", output);
-    let v = parse_envelope(&json_str);
-    let json_records = records(&v);
+    assert_eq!(recs.len(), 4);
 
-    let direct_records = netcli_core::parse_records("cisco_ios", "show_version", output).unwrap();
+    assert_eq!(recs[0].get("route_distinguisher").unwrap(), "65000:100");
+    assert_eq!(recs[0].get("vrf").unwrap(), "CUST_A");
+    assert_eq!(recs[0].get("network").unwrap(), "10.10.1.0/24");
+    assert_eq!(recs[1].get("status_codes").unwrap(), "*>i");
+    assert_eq!(recs[1].get("network").unwrap(), "10.10.2.0/24");
+    assert_eq!(recs[1].get("next_hop").unwrap(), "10.0.0.2");
 
-    assert_eq!(json_records.len(), direct_records.len());
-    for (jr, dr) in json_records.iter().zip(direct_records.iter()) {
-        for (key, value) in dr {
-            assert_eq!(jr[key], *value, "mismatch for key {key}");
-        }
-    }
+    assert_eq!(recs[2].get("route_distinguisher").unwrap(), "65000:200");
+    assert_eq!(recs[2].get("vrf").unwrap(), "");
+    assert_eq!(recs[2].get("path").unwrap(), "65200");
+    assert_eq!(recs[3].get("network").unwrap(), "172.31.5.0/24", "second path fills down the network");
+    assert_eq!(recs[3].get("next_hop").unwrap(), "10.0.0.4");
+    assert_eq!(recs[3].get("origin").unwrap(), "i");
 }
 
 #[test]
-fn command_and_key_apis_produce_identical_records_for_multiple_platforms() {
-    let platforms_and_commands: Vec<(&str, &str, &str)> = vec![
-        ("cisco_ios", "show_version", "show version"),
-        ("arista_eos", "show_version", "show version"),
-        ("cisco_nxos", "show_version", "show version"),
-    ];
+fn cisco_xr_show_interface_accounting_per_protocol() {
+    let output = include_str!("fixtures/cisco_xr/show_interface_accounting.txt");
+    let recs = netcli_core::parse_records("cisco_xr", "show_interface_accounting", output).unwrap();
 
-    for (platform, key, cmd) in platforms_and_commands {
-        let fixture = match (platform, key) {
-            ("cisco_ios", "show_version") => include_str!("fixtures/cisco_ios/show_version.txt"),
-            ("arista_eos", "show_version") => include_str!("fixtures/arista_eos/show_version.txt"),
-            ("cisco_nxos", "show_version") => include_str!("fixtures/cisco_nxos/show_version.txt"),
-            _ => unreachable!(),
-        };
+    assert_eq!(recs.len(), 6, "interfaces without traffic produce no rows");
+    let (v4, v6) = (&recs[0], &recs[1]);
+    assert_eq!(v4.get("interface").unwrap(), "GigabitEthernet0/0/0/0");
+    assert_eq!(v4.get("protocol").unwrap(), "IPV4_UNICAST");
+    assert_eq!(v4.get("pkts_in").unwrap(), "1234567");
+    assert_eq!(v4.get("chars_out").unwrap(), "123456789");
+    assert_eq!(v6.get("interface").unwrap(), "GigabitEthernet0/0/0/0", "interface fills down");
+    assert_eq!(v6.get("protocol").unwrap(), "IPV6_UNICAST");
+    assert_eq!(v6.get("pkts_out").unwrap(), "5432");
 
-        let by_key = netcli_core::parse_records(platform, key, fixture).unwrap();
-        let by_cmd = netcli_core::parse_command_records(platform, cmd, fixture).unwrap();
+    assert_eq!(recs[4].get("interface").unwrap(), "GigabitEthernet0/0/0/1");
+}
 
-        assert_eq!(by_key.len(), by_cmd.len(), "record count mismatch for {platform}/{key}");
-        assert_eq!(by_key, by_cmd, "records differ for {platform}/{key}");
-    }
+#[test]
+fn cisco_xr_show_controllers_optics_dom() {
+    let output = include_str!("fixtures/cisco_xr/show_controllers_optics.txt");
+    let recs = netcli_core::parse_records("cisco_xr", "show_controllers_optics", output).unwrap();
+
+    assert_eq!(recs.len(), 6, "4 lane rows and 1 optic row for 0/0/0/0, 1 optic row for 0/0/0/1");
+    let optics: Vec<_> = recs.iter().filter(|r| r.get("lane").unwrap().is_empty()).collect();
+    assert_eq!(optics.len(), 2);
+
+    let lr4 = optics[0];
+    assert_eq!(lr4.get("port").unwrap(), "0/0/0/0");
+    assert_eq!(lr4.get("optics_type").unwrap(), "100G QSFP28 LR4");
+    assert_eq!(lr4.get("laser_state").unwrap(), "On");
+    assert_eq!(lr4.get("tx_power").unwrap(), "6.98");
+    assert_eq!(lr4.get("rx_power").unwrap(), "4.17");
+    assert_eq!(lr4.get("temperature").unwrap(), "33.00");
+    assert_eq!(lr4.get("voltage").unwrap(), "3.30");
+    assert_eq!(lr4.get("vendor_serial").unwrap(), "FNS22021ABC");
+
+    let lane2 = &recs[2];
+    assert_eq!(lane2.get("port").unwrap(), "0/0/0/0");
+    assert_eq!(lane2.get("lane").unwrap(), "2");
+    assert_eq!(lane2.get("lane_tx_power").unwrap(), "1.12");
+    assert_eq!(lane2.get("lane_rx_power").unwrap(), "-1.87");
+
+    let lr = optics[1];
+    assert_eq!(lr.get("port").unwrap(), "0/0/0/1");
+    assert_eq!(lr.get("laser_state").unwrap(), "On");
+    assert_eq!(lr.get("tx_power").unwrap(), "-2.10");
+    assert_eq!(lr.get("rx_power").unwrap(), "-13.47");
+    assert_eq!(lr.get("laser_bias_current").unwrap(), "32.6");
 }
 
-// ========================================================================
-// cisco_ios fixture tests
-// ========================================================================
+#[test]
+fn cisco_xr_show_controllers_optics_without_prompt_still_splits_per_optic() {
+    let output = include_str!("fixtures/cisco_xr/show_controllers_optics_no_prompt.txt");
+    let recs = netcli_core::parse_records("cisco_xr", "show_controllers_optics", output).unwrap();
+
+    assert_eq!(recs.len(), 6);
+    let optics: Vec<_> = recs.iter().filter(|r| r.get("lane").unwrap().is_empty()).collect();
+    assert_eq!(optics.len(), 2);
+    assert_eq!(optics[0].get("vendor_serial").unwrap(), "FNS22021ABC");
+    assert_eq!(optics[0].get("tx_power").unwrap(), "6.98");
+    assert_eq!(optics[1].get("vendor_serial").unwrap(), "AVD2117K0QX");
+    assert_eq!(optics[1].get("tx_power").unwrap(), "-2.10");
+    assert!(recs.iter().all(|r| r.get("port").unwrap().is_empty()), "port only comes from the prompt");
+}
 
 #[test]
-fn cisco_ios_show_ip_interface_brief() {
-    let output = include_str!("fixtures/cisco_ios/show_ip_interface_brief.txt");
-    let recs = netcli_core::parse_records("cisco_ios", "show_ip_interface_brief", output).unwrap();
+fn cisco_xr_show_isis_neighbors() {
+    let output = include_str!("fixtures/cisco_xr/show_isis_neighbors.txt");
+    let recs = netcli_core::parse_command_records("cisco_xr", "show isis neighbors", output).unwrap();
 
-    assert_eq!(recs.len(), 12);
-    assert_eq!(recs[0].get("interface").unwrap(), "GigabitEthernet0/0");
-    assert_eq!(recs[0].get("ip_address").unwrap(), "10.1.1.1");
-    assert_eq!(recs[0].get("status").unwrap(), "up");
-    assert_eq!(recs[0].get("proto").unwrap(), "up");
+    assert_eq!(recs.len(), 3);
+    let p1 = &recs[0];
+    assert_eq!(p1.get("instance").unwrap(), "CORE");
+    assert_eq!(p1.get("system_id").unwrap(), "p1");
+    assert_eq!(p1.get("interface").unwrap(), "Hu0/0/0/0");
+    assert_eq!(p1.get("state").unwrap(), "Up");
+    assert_eq!(p1.get("type").unwrap(), "L2");
+    assert_eq!(p1.get("hold_time").unwrap(), "27");
+    assert_eq!(recs[2].get("type").unwrap(), "L1L2");
+    assert_eq!(recs[2].get("state").unwrap(), "Init");
 }
 
 // ========================================================================
@@ -597,7 +2301,23 @@ fn cisco_iosxe_show_version() {
     assert_eq!(recs[0]["hostname"], "my-cisco-device");
     assert_eq!(recs[0]["version"], "17.9.5a");
     assert_eq!(recs[0]["software_image"], "X86_64_LINUX_IOSD-UNIVERSALK9-M");
-    assert_eq!(recs[0]["serial"], "[FLM292210DA]");
+    assert_eq!(recs[0]["serial"], serde_json::json!(["FLM292210DA"]));
+}
+
+#[test]
+fn cisco_iosxe_show_version_isr4k_license_table() {
+    // The license table runs straight into the hardware line, with no blank line.
+    let output = include_str!("fixtures/cisco_iosxe/show_version_isr4k.txt");
+    let v = parse_envelope(&netcli_core::parse_json("cisco_iosxe", "show_version", output));
+
+    assert_success(&v);
+    let rec = &records(&v)[0];
+    assert_eq!(rec["hardware"], serde_json::json!(["ISR4331/K9"]));
+    assert_eq!(rec["serial"], serde_json::json!(["FDO21520TGH"]));
+    assert_eq!(rec["config_register"], "0x2102");
+    assert_eq!(rec["feature"], serde_json::json!(["appxk9", "uck9", "securityk9", "ipbase"]));
+    assert_eq!(rec["license_level"], serde_json::json!(["appxk9", "None", "securityk9", "ipbasek9"]));
+    assert_eq!(rec["license_type"], serde_json::json!(["RightToUse", "None", "Permanent", "Permanent"]));
 }
 
 #[test]
@@ -636,6 +2356,46 @@ fn cisco_iosxe_show_interfaces_transceiver() {
     assert_eq!(recs[0].get("rx_power").unwrap(), "-3.4");
 }
 
+#[test]
+fn cisco_iosxe_interfaces_status_joined_with_transceiver() {
+    use netcli_core::normalize::normalize_interface_name;
+
+    let canonical_ports = |mut recs: Vec<std::collections::HashMap<String, String>>| {
+        for rec in &mut recs {
+            let port = normalize_interface_name(&rec["port"]);
+            rec.insert("port".into(), port);
+        }
+        recs
+    };
+    let status = canonical_ports(
+        netcli_core::parse_records(
+            "cisco_iosxe",
+            "show_interfaces_status",
+            include_str!("fixtures/cisco_iosxe/show_interfaces_status.txt"),
+        )
+        .unwrap(),
+    );
+    let optics = canonical_ports(
+        netcli_core::parse_records(
+            "cisco_iosxe",
+            "show_interfaces_transceiver",
+            include_str!("fixtures/cisco_iosxe/show_interfaces_transceiver.txt"),
+        )
+        .unwrap(),
+    );
+
+    let ports = netcli_core::join_records(&status, &optics, "port");
+
+    assert_eq!(ports.len(), 8, "copper ports have no transceiver row");
+    assert_eq!(ports[0]["port"], "GigabitEthernet0/0/0");
+    assert_eq!(ports[0]["status"], "connected");
+    assert_eq!(ports[0]["type"], "1000BaseSX");
+    assert_eq!(ports[0]["vendor"], "CISCO-FINI");
+    assert_eq!(ports[6]["port"], "HundredGigE0/0/0");
+    assert_eq!(ports[6]["name"], "100G-Core");
+    assert_eq!(ports[6]["xcvr_type"], "QSFP28");
+}
+
 #[test]
 fn cisco_iosxe_show_inventory() {
     let output = include_str!("fixtures/cisco_iosxe/show_inventory.txt");
@@ -717,6 +2477,26 @@ fn cisco_iosxe_show_interfaces_transceiver_detail() {
     assert_eq!(recs[0].get("rx_power").unwrap(), "-3.4");
 }
 
+#[test]
+fn cisco_iosxe_show_interfaces_transceiver_detail_per_lane() {
+    let output = include_str!("fixtures/cisco_iosxe/show_interfaces_transceiver_detail_lanes.txt");
+    let recs = netcli_core::parse_records("cisco_iosxe", "show_interfaces_transceiver_detail", output).unwrap();
+
+    assert_eq!(recs.len(), 6);
+    let lanes: Vec<_> = recs.iter().filter(|r| r["port"] == "Hu0/1/0").collect();
+    assert_eq!(lanes.len(), 4, "one record per QSFP lane");
+    let numbers: Vec<&str> = lanes.iter().map(|r| r["lane"].as_str()).collect();
+    assert_eq!(numbers, ["1", "2", "3", "4"]);
+    assert_eq!(lanes[1]["bias_current"], "6.8");
+    assert_eq!(lanes[1]["tx_power"], "-0.6");
+    assert_eq!(lanes[3]["rx_power"], "-2.0");
+    assert_eq!(lanes[3]["temperature"], "34.2", "module readings fill down to each lane");
+
+    assert_eq!(recs[0]["lane"], "", "single-lane optics have no lane");
+    assert_eq!(recs[5]["port"], "Te0/0/1");
+    assert_eq!(recs[5]["tx_power"], "-2.3");
+}
+
 #[test]
 fn cisco_iosxe_show_platform_resources() {
     let output = include_str!("fixtures/cisco_iosxe/show_platform_resources.txt");
@@ -908,6 +2688,72 @@ fn dnos_show_interfaces_transceiver() {
     assert_eq!(recs[0].get("vendor_sn").unwrap(), "U4DADNU");
 }
 
+#[test]
+fn dnos_show_interfaces_transceiver_dom_thresholds() {
+    let output = include_str!("fixtures/drivenets_dnos/show_interfaces_transceiver.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_interfaces_transceiver", output).unwrap();
+
+    let port = &recs[1];
+    assert_eq!(port.get("interface").unwrap(), "ge100-0/0/1");
+    assert_eq!(port.get("temperature").unwrap(), "32.9");
+    assert_eq!(port.get("module_temperature").unwrap(), "32.9 degrees C / 91.1 degrees F");
+    assert_eq!(port.get("voltage").unwrap(), "3.3");
+    assert_eq!(port.get("rx_power").unwrap(), "[2.4, 2.5, 2.9, 3.0]", "one reading per lane");
+    assert_eq!(port.get("tx_power").unwrap(), "[2.7, 2.7, 2.8, 3.0]");
+    assert_eq!(port.get("rx_power_low_alarm").unwrap(), "-14.6");
+    assert_eq!(port.get("rx_power_high_warning").unwrap(), "4.5");
+    assert_eq!(port.get("tx_power_low_alarm").unwrap(), "-8.3");
+    assert_eq!(port.get("temperature_high_alarm").unwrap(), "75.0");
+    assert_eq!(port.get("voltage_low_warning").unwrap(), "3.1");
+
+    let empty = recs.iter().find(|r| r.get("interface").unwrap() == "ge100-0/0/5").unwrap();
+    assert_eq!(empty.get("rx_power").unwrap(), "[]", "no optic, no readings");
+    assert_eq!(empty.get("rx_power_low_alarm").unwrap(), "");
+}
+
+#[test]
+fn dnos_show_interfaces_transceiver_alarms_check_every_lane() {
+    let output = include_str!("fixtures/drivenets_dnos/show_interfaces_transceiver.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_interfaces_transceiver", output).unwrap();
+
+    let alarms = netcli_core::transceiver_alarms(&recs);
+    let dark: Vec<&str> = alarms.iter().map(|(port, _)| port.as_str()).collect();
+    assert_eq!(dark, ["ge100-0/0/3", "ge100-0/0/4", "ge100-0/0/39"], "lanes at -40 dBm");
+    assert!(alarms.iter().all(|(_, breaches)| breaches == &["rx_power_low"]), "{alarms:?}");
+}
+
+#[test]
+fn dnos_show_interface_fec() {
+    let output = include_str!("fixtures/drivenets_dnos/show_interface_fec.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_interface_fec", output).unwrap();
+
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("interface").unwrap(), "ge400-0/0/4");
+    assert_eq!(recs[0].get("fec_mode").unwrap(), "rs-fec-544-514");
+    assert_eq!(recs[0].get("corrected_codewords").unwrap(), "1823347");
+    assert_eq!(recs[0].get("uncorrected_codewords").unwrap(), "4");
+    assert_eq!(recs[0].get("symbol_errors").unwrap(), "2041785");
+}
+
+#[test]
+fn dnos_show_interface_fec_counters() {
+    let output = include_str!("fixtures/drivenets_dnos/show_interface_fec_counters.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_interface_fec_counters", output).unwrap();
+
+    assert_eq!(recs.len(), 4);
+    let port = &recs[1];
+    assert_eq!(port.get("interface").unwrap(), "ge100-0/0/1");
+    assert_eq!(port.get("corrected_codewords").unwrap(), "5120");
+    assert_eq!(port.get("uncorrected_codewords").unwrap(), "0");
+    assert_eq!(port.get("symbol_errors").unwrap(), "5233");
+    assert_eq!(port.get("pre_fec_ber").unwrap(), "2.7e-12");
+
+    let no_fec = &recs[3];
+    assert_eq!(no_fec.get("interface").unwrap(), "ge10-0/0/12");
+    assert_eq!(no_fec.get("fec_mode").unwrap(), "none");
+    assert_eq!(no_fec.get("corrected_codewords").unwrap(), "", "no counters without FEC");
+}
+
 #[test]
 fn dnos_show_config_flatten() {
     let output = include_str!("fixtures/drivenets_dnos/show_config_flatten.txt");
@@ -918,6 +2764,23 @@ fn dnos_show_config_flatten() {
     assert_eq!(recs[1].get("line").unwrap(), "interface Management0");
 }
 
+#[test]
+fn dnos_show_config_flatten_one_record_per_line_in_order() {
+    let output = include_str!("fixtures/drivenets_dnos/show_config_flatten.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_config_flatten", output).unwrap();
+
+    let expected: Vec<&str> = output
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('!'))
+        .collect();
+    assert_eq!(expected.len(), 36);
+    assert_eq!(recs.len(), expected.len());
+    for (rec, line) in recs.iter().zip(&expected) {
+        assert_eq!(rec.len(), 1, "whole-line records carry a single field");
+        assert_eq!(rec.get("line").unwrap(), line);
+    }
+}
+
 #[test]
 fn dnos_show_lldp() {
     let output = include_str!("fixtures/drivenets_dnos/show_lldp.txt");
@@ -968,6 +2831,71 @@ fn dnos_show_route_summary() {
     assert_eq!(recs[5].get("route_source").unwrap(), "Total");
 }
 
+#[test]
+fn route_summary_normalized_across_platforms() {
+    let options = netcli_core::ParseOptions {
+        normalize: true,
+        ..Default::default()
+    };
+    let cases = [
+        (
+            "cisco_iosxe",
+            "show_ip_route_summary",
+            include_str!("fixtures/cisco_iosxe/show_ip_route_summary.txt"),
+            "1248",
+        ),
+        (
+            "drivenets_dnos",
+            "show_route_summary",
+            include_str!("fixtures/drivenets_dnos/show_route_summary.txt"),
+            "1250",
+        ),
+    ];
+    for (platform, key, output, bgp_prefixes) in cases {
+        let v = parse_envelope(&netcli_core::parse_json_with_options(platform, key, output, &options));
+        assert_success(&v);
+        let recs = records(&v);
+
+        let bgp = recs.iter().find(|r| r["route_source"].as_str().unwrap().starts_with("bgp")).unwrap();
+        assert_eq!(bgp["prefix_count"], bgp_prefixes, "{platform}");
+        assert_eq!(bgp["total"], "false", "{platform}");
+
+        let (totals, rows): (Vec<_>, Vec<_>) = recs.iter().partition(|r| r["total"] == "true");
+        assert_eq!(totals.len(), 1, "{platform}: exactly one total row");
+        let sum: u64 = rows
+            .iter()
+            .map(|r| r["prefix_count"].as_str().unwrap().parse::<u64>().unwrap())
+            .sum();
+        assert_eq!(totals[0]["prefix_count"], sum.to_string(), "{platform}: total matches rows");
+    }
+}
+
+#[test]
+fn dnos_show_route_summary_synthesizes_total() {
+    let output: String = include_str!("fixtures/drivenets_dnos/show_route_summary.txt")
+        .lines()
+        .filter(|l| !l.starts_with("Total"))
+        .map(|l| format!("{l}\n"))
+        .collect();
+    let options = netcli_core::ParseOptions {
+        normalize: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options(
+        "drivenets_dnos",
+        "show_route_summary",
+        &output,
+        &options,
+    ));
+
+    assert_success(&v);
+    let recs = records(&v);
+    assert_eq!(recs.len(), 6);
+    assert_eq!(recs[5]["route_source"], "Total");
+    assert_eq!(recs[5]["prefix_count"], "1355");
+    assert_eq!(recs[5]["path_count"], "2605");
+}
+
 #[test]
 fn dnos_show_system_hardware() {
     let output = include_str!("fixtures/drivenets_dnos/show_system_hardware.txt");
@@ -992,6 +2920,18 @@ fn dnos_show_system_hardware_cpu() {
     assert_eq!(recs[15].get("use_percent").unwrap(), "45");
 }
 
+#[test]
+fn dnos_show_system_hardware_cpu_as_prometheus() {
+    let output = include_str!("fixtures/drivenets_dnos/show_system_hardware_cpu.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_system_hardware_cpu", output).unwrap();
+    let text = netcli_core::records_to_prometheus("cpu", &recs, "use_percent", &["cpu"]);
+
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 16);
+    assert_eq!(lines[0], r#"cpu_use_percent{cpu="0"} 28"#);
+    assert_eq!(lines[15], r#"cpu_use_percent{cpu="15"} 45"#);
+}
+
 #[test]
 fn dnos_show_system_hardware_fan() {
     let output = include_str!("fixtures/drivenets_dnos/show_system_hardware_fan.txt");
@@ -1021,11 +2961,33 @@ fn dnos_show_system_hardware_power() {
     let output = include_str!("fixtures/drivenets_dnos/show_system_hardware_power.txt");
     let recs = netcli_core::parse_records("drivenets_dnos", "show_system_hardware_power", output).unwrap();
 
-    assert_eq!(recs.len(), 2);
+    assert_eq!(recs.len(), 3);
+    assert_eq!(recs[0].get("psu_id").unwrap(), "0");
+    assert_eq!(recs[0].get("status").unwrap(), "OK");
+    assert_eq!(recs[0].get("type").unwrap(), "AC 110V-220V");
+    assert_eq!(recs[0].get("serial").unwrap(), "S0A030Z851915000883");
+}
+
+#[test]
+fn dnos_show_system_hardware_psu() {
+    let output = include_str!("fixtures/drivenets_dnos/show_system_hardware_psu.txt");
+    let recs = netcli_core::parse_records("drivenets_dnos", "show_system_hardware_psu", output).unwrap();
+
+    assert_eq!(recs.len(), 3);
     assert_eq!(recs[0].get("psu_id").unwrap(), "0");
     assert_eq!(recs[0].get("status").unwrap(), "OK");
     assert_eq!(recs[0].get("type").unwrap(), "AC 110V-220V");
+    assert_eq!(recs[0].get("input_voltage").unwrap(), "229.5");
+    assert_eq!(recs[0].get("output_power").unwrap(), "412.0");
     assert_eq!(recs[0].get("serial").unwrap(), "S0A030Z851915000883");
+    assert_eq!(recs[1].get("psu_id").unwrap(), "1");
+    assert_eq!(recs[1].get("status").unwrap(), "OK");
+    assert_eq!(recs[1].get("serial").unwrap(), "S0A030Z851915000884");
+    // The same table without borders.
+    assert_eq!(recs[2].get("psu_id").unwrap(), "0");
+    assert_eq!(recs[2].get("type").unwrap(), "AC 110V-220V");
+    assert_eq!(recs[2].get("output_power").unwrap(), "405.5");
+    assert_eq!(recs[2].get("serial").unwrap(), "S0A030Z851915000901");
 }
 
 #[test]
@@ -1041,6 +3003,29 @@ fn dnos_show_system_hardware_temperature() {
     assert_eq!(recs[0].get("high_critical").unwrap(), "70");
 }
 
+#[test]
+fn dnos_show_system_hardware_temperature_normalized_state() {
+    let output = include_str!("fixtures/drivenets_dnos/show_system_hardware_temperature.txt");
+    let options = netcli_core::ParseOptions {
+        normalize: true,
+        ..Default::default()
+    };
+    let v = parse_envelope(&netcli_core::parse_json_with_options(
+        "drivenets_dnos",
+        "show_system_hardware_temperature",
+        output,
+        &options,
+    ));
+
+    assert_success(&v);
+    let recs = records(&v);
+    assert_eq!(recs.len(), 20);
+    assert!(recs.iter().all(|r| r["temperature_state"] == "ok"));
+
+    let plain = netcli_core::parse_records("drivenets_dnos", "show_system_hardware_temperature", output).unwrap();
+    assert!(!plain[0].contains_key("temperature_state"), "derived fields only with normalize on");
+}
+
 // ========================================================================
 // DNOS command API test
 // ========================================================================
@@ -1055,6 +3040,59 @@ fn dnos_command_api_show_system_version() {
     assert_eq!(records(&v)[0]["system_name"], "DN-SA-01");
 }
 
+// ========================================================================
+// Fortinet FortiOS fixture tests
+// ========================================================================
+
+#[test]
+fn fortinet_get_system_status() {
+    let output = include_str!("fixtures/fortinet/get_system_status.txt");
+    let recs = netcli_core::parse_command_records("fortigate", "get system status", output).unwrap();
+
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].get("version").unwrap(), "FortiGate-100F v7.2.8,build1639,240313 (GA.M)");
+    assert_eq!(recs[0].get("serial").unwrap(), "FG100FTK21001234");
+    assert_eq!(recs[0].get("hostname").unwrap(), "FGT-EDGE-01");
+    assert_eq!(recs[0].get("bios_version").unwrap(), "05000014");
+    assert_eq!(recs[0].get("ha_mode").unwrap(), "a-p, primary");
+    assert_eq!(recs[0].get("serial_number").unwrap(), "FG100FTK21001234", "existing field kept");
+    assert_eq!(recs[0].get("current_ha_mode").unwrap(), "a-p, primary", "existing field kept");
+
+    let show_version = netcli_core::parse_records("fortinet", "show_version", output).unwrap();
+    assert_eq!(show_version, recs, "show_version maps to the same template");
+}
+
+#[test]
+fn fortinet_aliases_resolve_show_version() {
+    let output = include_str!("fixtures/fortinet/get_system_status.txt");
+    let canonical = netcli_core::parse_records("fortinet", "show_version", output).unwrap();
+    for alias in ["fortios", "fortigate"] {
+        let recs = netcli_core::parse_records(alias, "show_version", output).unwrap();
+        assert_eq!(recs, canonical, "{alias} should alias to fortinet");
+    }
+}
+
+#[test]
+fn paloalto_panos_show_system_info() {
+    let output = include_str!("fixtures/paloalto_panos/show_system_info.txt");
+    let rec = netcli_core::parse_single("paloalto_panos", "show_system_info", output)
+        .unwrap()
+        .expect("one record");
+
+    assert_eq!(rec["hostname"], "PA-EDGE-01");
+    assert_eq!(rec["model"], "PA-3220");
+    assert_eq!(rec["serial"], "012801012345");
+    assert_eq!(rec["os"], "10.2.4-h4", "sw-version");
+    assert_eq!(rec["uptime"], "45 days, 3:12:05");
+    assert_eq!(rec["mac_address"], "00:1b:17:00:01:10");
+    assert_eq!(rec["operational_mode"], "normal");
+
+    for alias in ["panos", "palo_alto"] {
+        let recs = netcli_core::parse_command_records(alias, "show system info", output).unwrap();
+        assert_eq!(recs, [rec.clone()], "{alias} should alias to paloalto_panos");
+    }
+}
+
 // ========================================================================
 // Phase 1 tests: normalize_command fixes (hyphens, pipes, abbreviations)
 // ========================================================================
@@ -1118,6 +3156,16 @@ fn command_api_mac_address_table_with_hyphens() {
     assert_eq!(v["ok"], true, "show_mac_address_table should resolve via hyphen normalization");
 }
 
+#[test]
+fn command_api_mac_address_table_qualified_variants() {
+    let output = "some dummy text\n";
+    for command in ["show mac address-table dynamic", "show mac address-table static"] {
+        let v = parse_envelope(&netcli_core::parse_command_json("cisco_ios", command, output));
+        assert_eq!(v["ok"], true, "{command} should use the unqualified template");
+        assert_eq!(v["commandKey"], "show_mac_address_table");
+    }
+}
+
 #[test]
 fn command_api_spanning_tree_with_hyphens() {
     let output = "some dummy text\n";
@@ -1,6 +1,16 @@
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
-use std::panic;
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    /// Error code of the last failed [`netcli_parse_records_json`] call on this thread.
+    static LAST_ERROR_CODE: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error_code(code: Option<&str>) {
+    LAST_ERROR_CODE.with(|slot| *slot.borrow_mut() = code.and_then(|c| CString::new(c).ok()));
+}
 
 unsafe fn cstr_to_str<'a>(ptr: *const c_char) -> &'a str {
     if ptr.is_null() {
@@ -12,6 +22,19 @@ unsafe fn cstr_to_str<'a>(ptr: *const c_char) -> &'a str {
     }
 }
 
+/// Decode a null-terminated UTF-16 string. Null pointers and invalid
+/// UTF-16 (unpaired surrogates) decode to an empty string.
+unsafe fn utf16_to_string(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    String::from_utf16(std::slice::from_raw_parts(ptr, len)).unwrap_or_default()
+}
+
 fn error_envelope(code: &str, message: &str) -> *const c_char {
     let json = format!(
         r#"{{"ok":false,"error":{{"code":"{code}","message":"{message}"}}}}"#
@@ -81,10 +104,234 @@ pub unsafe extern "C" fn netcli_parse_command_json(
     }
 }
 
-/// Free a string previously returned by [`netcli_parse_json`].
+/// UTF-16 variant of [`netcli_parse_json`] for callers (e.g. .NET on Windows)
+/// that hold UTF-16 strings, in native byte order (UTF-16LE on Windows).
+///
+/// The returned JSON is UTF-8, as with every other entry point.
 ///
 /// # Safety
-/// `s` must be a pointer previously returned by `netcli_parse_json`, or null.
+/// All pointer arguments must be valid, null-terminated UTF-16 strings (or null).
+/// The caller **must** free the returned pointer with [`netcli_free`].
+#[no_mangle]
+pub unsafe extern "C" fn netcli_parse_json_utf16(
+    platform: *const u16,
+    command_key: *const u16,
+    output_text: *const u16,
+) -> *const c_char {
+    let result = panic::catch_unwind(|| {
+        let p = utf16_to_string(platform);
+        let ck = utf16_to_string(command_key);
+        let ot = utf16_to_string(output_text);
+
+        netcli_core::parse_json(&p, &ck, &ot)
+    });
+
+    match result {
+        Ok(json_string) => match CString::new(json_string) {
+            Ok(cs) => cs.into_raw() as *const c_char,
+            Err(_) => error_envelope("INTERNAL_ERROR", "JSON contained interior NUL byte"),
+        },
+        Err(_) => error_envelope("INTERNAL_ERROR", "Internal panic caught at FFI boundary"),
+    }
+}
+
+fn error_json(code: &str, message: &str) -> String {
+    serde_json::json!({ "ok": false, "error": { "code": code, "message": message } }).to_string()
+}
+
+/// Parse many outputs of the same command in one call.
+///
+/// `outputs_json` is a JSON array of output strings; the result is a JSON
+/// array holding one envelope per element, in the same order. The template
+/// is resolved and compiled once for the whole batch. An element that is not
+/// a string, or whose parse panics, gets an error envelope in its slot
+/// without affecting the others. If `outputs_json` is not a JSON array, a
+/// single `INVALID_INPUT` error envelope is returned instead of an array.
+///
+/// # Safety
+/// All pointer arguments must be valid, null-terminated C strings (or null).
+/// The caller **must** free the returned pointer with [`netcli_free`].
+#[no_mangle]
+pub unsafe extern "C" fn netcli_parse_batch_json(
+    platform: *const c_char,
+    command_key: *const c_char,
+    outputs_json: *const c_char,
+) -> *const c_char {
+    let result = panic::catch_unwind(|| {
+        let p = cstr_to_str(platform);
+        let ck = cstr_to_str(command_key);
+        let outputs: Vec<serde_json::Value> = serde_json::from_str(cstr_to_str(outputs_json)).ok()?;
+
+        let options = netcli_core::ParseOptions::default();
+        let prepared = netcli_core::prepare(p, ck).map_err(|e| error_json(e.code(), &e.to_string()));
+
+        let envelopes: Vec<String> = outputs
+            .iter()
+            .map(|output| {
+                let Some(output) = output.as_str() else {
+                    return error_json("INVALID_INPUT", "batch element is not a string");
+                };
+                match &prepared {
+                    Ok(prepared) => panic::catch_unwind(AssertUnwindSafe(|| prepared.parse_json(output, &options)))
+                        .unwrap_or_else(|_| error_json("INTERNAL_ERROR", "Internal panic caught at FFI boundary")),
+                    Err(envelope) => envelope.clone(),
+                }
+            })
+            .collect();
+        Some(format!("[{}]", envelopes.join(",")))
+    });
+
+    match result {
+        Ok(Some(json_string)) => match CString::new(json_string) {
+            Ok(cs) => cs.into_raw() as *const c_char,
+            Err(_) => error_envelope("INTERNAL_ERROR", "JSON contained interior NUL byte"),
+        },
+        Ok(None) => error_envelope("INVALID_INPUT", "outputs_json must be a JSON array of strings"),
+        Err(_) => error_envelope("INTERNAL_ERROR", "Internal panic caught at FFI boundary"),
+    }
+}
+
+/// Run a caller-supplied TextFSM template over `output_text`, with no registry
+/// lookup, using the same engine as the registry-backed entry points (see
+/// `netcli_core::run_template`).
+///
+/// Returns `{"ok":true,"records":[...]}` or the usual error envelope, with
+/// records shaped as in [`netcli_parse_json`] (`List` values as JSON arrays).
+/// There is no `platform`/`commandKey`, since no registry entry is involved.
+///
+/// # Safety
+/// All pointer arguments must be valid, null-terminated C strings (or null).
+/// The caller **must** free the returned pointer with [`netcli_free`].
+#[no_mangle]
+pub unsafe extern "C" fn netcli_run_template_json(
+    template_text: *const c_char,
+    output_text: *const c_char,
+) -> *const c_char {
+    let result = panic::catch_unwind(|| {
+        let tt = cstr_to_str(template_text);
+        let ot = cstr_to_str(output_text);
+
+        netcli_core::run_template_json(tt, ot, &netcli_core::ParseOptions::default())
+    });
+
+    match result {
+        Ok(json_string) => match CString::new(json_string) {
+            Ok(cs) => cs.into_raw() as *const c_char,
+            Err(_) => error_envelope("INTERNAL_ERROR", "JSON contained interior NUL byte"),
+        },
+        Err(_) => error_envelope("INTERNAL_ERROR", "Internal panic caught at FFI boundary"),
+    }
+}
+
+/// Parse CLI output and return only the records as a bare JSON array
+/// (`[{...}, ...]`, shaped as in [`netcli_parse_json`]), without the envelope.
+///
+/// Returns null on error; [`netcli_last_error_code`] then reports why.
+///
+/// # Safety
+/// All pointer arguments must be valid, null-terminated C strings (or null).
+/// The caller **must** free a non-null result with [`netcli_free`].
+#[no_mangle]
+pub unsafe extern "C" fn netcli_parse_records_json(
+    platform: *const c_char,
+    command_key: *const c_char,
+    output_text: *const c_char,
+) -> *const c_char {
+    let result = panic::catch_unwind(|| {
+        let p = cstr_to_str(platform);
+        let ck = cstr_to_str(command_key);
+        let ot = cstr_to_str(output_text);
+
+        // NDJSON lines serialize exactly as `netcli_parse_json` records would,
+        // key order and `List` arrays included; a `Value` would re-sort keys.
+        match netcli_core::parse_records_ndjson(p, ck, ot) {
+            Ok(ndjson) => Ok(format!("[{}]", ndjson.lines().collect::<Vec<_>>().join(","))),
+            Err(e) => Err(e.code().to_string()),
+        }
+    });
+
+    let json_string = match result {
+        Ok(Ok(json_string)) => json_string,
+        Ok(Err(code)) => {
+            set_last_error_code(Some(&code));
+            return std::ptr::null();
+        }
+        Err(_) => {
+            set_last_error_code(Some("INTERNAL_ERROR"));
+            return std::ptr::null();
+        }
+    };
+
+    match CString::new(json_string) {
+        Ok(cs) => {
+            set_last_error_code(None);
+            cs.into_raw() as *const c_char
+        }
+        Err(_) => {
+            set_last_error_code(Some("INTERNAL_ERROR"));
+            std::ptr::null()
+        }
+    }
+}
+
+/// [`netcli_parse_json`] into a caller-owned buffer, for hosts that manage
+/// their own memory.
+///
+/// Writes the envelope and a terminating NUL into `buf` and returns the
+/// envelope's length in bytes (excluding the NUL). If it does not fit in
+/// `buf_len` bytes, nothing is written and the negated size needed
+/// (including the NUL) is returned, so the caller can grow the buffer and
+/// retry. A null `buf` with `buf_len` 0 just queries that size.
+///
+/// # Safety
+/// `platform`, `command_key` and `output_text` must be valid, null-terminated
+/// C strings (or null). `buf` must be valid for writes of `buf_len` bytes, or
+/// null with `buf_len` 0. Nothing needs freeing.
+#[no_mangle]
+pub unsafe extern "C" fn netcli_parse_into_buffer(
+    platform: *const c_char,
+    command_key: *const c_char,
+    output_text: *const c_char,
+    buf: *mut c_char,
+    buf_len: usize,
+) -> isize {
+    let result = panic::catch_unwind(|| {
+        let p = cstr_to_str(platform);
+        let ck = cstr_to_str(command_key);
+        let ot = cstr_to_str(output_text);
+
+        netcli_core::parse_json(p, ck, ot)
+    });
+    let json = result.unwrap_or_else(|_| error_json("INTERNAL_ERROR", "Internal panic caught at FFI boundary"));
+
+    let needed = json.len() + 1;
+    if buf.is_null() || buf_len < needed {
+        return -(needed as isize);
+    }
+    std::ptr::copy_nonoverlapping(json.as_ptr(), buf.cast::<u8>(), json.len());
+    *buf.add(json.len()) = 0;
+    json.len() as isize
+}
+
+/// Error code (one of `ParseError::CODES`, or `INTERNAL_ERROR`) of the last
+/// failed [`netcli_parse_records_json`] call on the calling thread, or null
+/// if that call succeeded.
+///
+/// The pointer is owned by the library and stays valid until the next call
+/// to `netcli_parse_records_json` on the same thread. Do not free it.
+#[no_mangle]
+pub extern "C" fn netcli_last_error_code() -> *const c_char {
+    LAST_ERROR_CODE.with(|slot| slot.borrow().as_ref().map_or(std::ptr::null(), |c| c.as_ptr()))
+}
+
+/// Free a string previously returned by [`netcli_parse_json`],
+/// [`netcli_parse_command_json`], [`netcli_parse_json_utf16`],
+/// [`netcli_parse_batch_json`], [`netcli_run_template_json`] or
+/// [`netcli_parse_records_json`].
+///
+/// # Safety
+/// `s` must be a pointer previously returned by one of those functions and not
+/// yet freed, or null.
 #[no_mangle]
 pub unsafe extern "C" fn netcli_free(s: *const c_char) {
     if !s.is_null() {
@@ -124,6 +371,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ffi_parse_into_buffer_writes_envelope_or_reports_size() {
+        let platform = make_c("cisco_ios");
+        let cmd = make_c("show_version");
+        let output = make_c("some device output");
+
+        unsafe {
+            let needed = netcli_parse_into_buffer(
+                platform.as_ptr(),
+                cmd.as_ptr(),
+                output.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+            );
+            assert!(needed < 0);
+
+            let mut small = vec![0 as c_char; 8];
+            let rc = netcli_parse_into_buffer(
+                platform.as_ptr(),
+                cmd.as_ptr(),
+                output.as_ptr(),
+                small.as_mut_ptr(),
+                small.len(),
+            );
+            assert_eq!(rc, needed, "too small reports the same required size");
+            assert!(small.iter().all(|&b| b == 0), "nothing written on failure");
+
+            let mut buf = vec![0 as c_char; needed.unsigned_abs()];
+            let written = netcli_parse_into_buffer(
+                platform.as_ptr(),
+                cmd.as_ptr(),
+                output.as_ptr(),
+                buf.as_mut_ptr(),
+                buf.len(),
+            );
+            assert_eq!(written, -needed - 1);
+
+            let json_str = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+            assert_eq!(json_str.len(), written as usize);
+            let v: serde_json::Value = serde_json::from_str(json_str).unwrap();
+            assert_eq!(v["ok"], true);
+            assert_eq!(v["platform"], "cisco_ios");
+        }
+    }
+
     #[test]
     fn ffi_null_platform_returns_error() {
         let cmd = make_c("show_version");
@@ -287,6 +579,199 @@ mod tests {
         }
     }
 
+    fn make_utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    #[test]
+    fn ffi_utf16_round_trip_success() {
+        let platform = make_utf16("cisco_ios");
+        let cmd = make_utf16("show_version");
+        let output = make_utf16("Cisco IOS Software, Version 15.2(4)E — ünïcode banner");
+
+        unsafe {
+            let ptr = netcli_parse_json_utf16(platform.as_ptr(), cmd.as_ptr(), output.as_ptr());
+            assert!(!ptr.is_null());
+
+            let json_str = CStr::from_ptr(ptr).to_str().unwrap();
+            let v: serde_json::Value = serde_json::from_str(json_str).unwrap();
+            assert_eq!(v["ok"], true);
+            assert_eq!(v["platform"], "cisco_ios");
+            assert_eq!(v["commandKey"], "show_version");
+
+            netcli_free(ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_utf16_null_output_returns_error() {
+        let platform = make_utf16("cisco_ios");
+        let cmd = make_utf16("show_version");
+
+        unsafe {
+            let ptr = netcli_parse_json_utf16(platform.as_ptr(), cmd.as_ptr(), std::ptr::null());
+            assert!(!ptr.is_null());
+
+            let json_str = CStr::from_ptr(ptr).to_str().unwrap();
+            let v: serde_json::Value = serde_json::from_str(json_str).unwrap();
+            assert_eq!(v["ok"], false);
+            assert_eq!(v["error"]["code"], "INVALID_INPUT");
+
+            netcli_free(ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_records_json_returns_bare_array() {
+        let platform = make_c("cisco_ios");
+        let cmd = make_c("show_version");
+        let output = make_c("Cisco IOS Software, Version 15.2(4)E");
+
+        unsafe {
+            let ptr = netcli_parse_records_json(platform.as_ptr(), cmd.as_ptr(), output.as_ptr());
+            assert!(!ptr.is_null());
+            assert!(netcli_last_error_code().is_null());
+
+            let json_str = CStr::from_ptr(ptr).to_str().unwrap();
+            assert!(json_str.starts_with('['), "expected a bare array, got: {json_str}");
+            let v: serde_json::Value = serde_json::from_str(json_str).unwrap();
+            assert!(v.is_array());
+
+            netcli_free(ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_records_json_error_returns_null_and_sets_code() {
+        let platform = make_c("nonexistent_os");
+        let cmd = make_c("show_version");
+        let output = make_c("some output");
+
+        unsafe {
+            let ptr = netcli_parse_records_json(platform.as_ptr(), cmd.as_ptr(), output.as_ptr());
+            assert!(ptr.is_null());
+            let code = CStr::from_ptr(netcli_last_error_code()).to_str().unwrap();
+            assert_eq!(code, "TEMPLATE_NOT_FOUND");
+
+            let platform = make_c("cisco_ios");
+            let ptr = netcli_parse_records_json(platform.as_ptr(), cmd.as_ptr(), std::ptr::null());
+            assert!(ptr.is_null());
+            assert_eq!(CStr::from_ptr(netcli_last_error_code()).to_str().unwrap(), "INVALID_INPUT");
+        }
+    }
+
+    #[test]
+    fn ffi_batch_returns_envelopes_in_order() {
+        let platform = make_c("cisco_ios");
+        let cmd = make_c("show_version");
+        let outputs = make_c(r#"["Cisco IOS Software, Version 15.2(4)E", "", 7, "Cisco IOS Software, Version 17.3"]"#);
+
+        unsafe {
+            let ptr = netcli_parse_batch_json(platform.as_ptr(), cmd.as_ptr(), outputs.as_ptr());
+            assert!(!ptr.is_null());
+
+            let json_str = CStr::from_ptr(ptr).to_str().unwrap();
+            let v: serde_json::Value = serde_json::from_str(json_str).unwrap();
+            let slots = v.as_array().unwrap();
+            assert_eq!(slots.len(), 4);
+            assert_eq!(slots[0]["ok"], true);
+            assert_eq!(slots[0]["commandKey"], "show_version");
+            assert_eq!(slots[1]["error"]["code"], "INVALID_INPUT");
+            assert_eq!(slots[2]["error"]["code"], "INVALID_INPUT");
+            assert_eq!(slots[3]["ok"], true, "earlier failures do not affect later slots");
+
+            netcli_free(ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_batch_unknown_platform_fills_every_slot() {
+        let platform = make_c("nonexistent_os");
+        let cmd = make_c("show_version");
+        let outputs = make_c(r#"["a", "b"]"#);
+
+        unsafe {
+            let ptr = netcli_parse_batch_json(platform.as_ptr(), cmd.as_ptr(), outputs.as_ptr());
+            let v: serde_json::Value = serde_json::from_str(CStr::from_ptr(ptr).to_str().unwrap()).unwrap();
+            let slots = v.as_array().unwrap();
+            assert_eq!(slots.len(), 2);
+            assert!(slots.iter().all(|s| s["error"]["code"] == "TEMPLATE_NOT_FOUND"));
+
+            netcli_free(ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_batch_rejects_non_array_input() {
+        let platform = make_c("cisco_ios");
+        let cmd = make_c("show_version");
+        let outputs = make_c(r#"{"not": "an array"}"#);
+
+        unsafe {
+            let ptr = netcli_parse_batch_json(platform.as_ptr(), cmd.as_ptr(), outputs.as_ptr());
+            let v: serde_json::Value = serde_json::from_str(CStr::from_ptr(ptr).to_str().unwrap()).unwrap();
+            assert_eq!(v["ok"], false);
+            assert_eq!(v["error"]["code"], "INVALID_INPUT");
+
+            let ptr2 = netcli_parse_batch_json(platform.as_ptr(), cmd.as_ptr(), std::ptr::null());
+            let v2: serde_json::Value = serde_json::from_str(CStr::from_ptr(ptr2).to_str().unwrap()).unwrap();
+            assert_eq!(v2["error"]["code"], "INVALID_INPUT");
+
+            netcli_free(ptr);
+            netcli_free(ptr2);
+        }
+    }
+
+    #[test]
+    fn ffi_run_template_honours_list_and_filldown() {
+        let template = make_c(
+            "Value Filldown VRF (\\S+)\n\
+             Value Required PREFIX (\\S+)\n\
+             Value List NEXT_HOP (\\S+)\n\
+             \n\
+             Start\n  \
+             ^VRF ${VRF}\n  \
+             ^\\S+\\s*$$ -> Continue.Record\n  \
+             ^${PREFIX}\\s*$$\n  \
+             ^\\s+via ${NEXT_HOP}\n",
+        );
+        let output = make_c("VRF red\n10.0.0.0/8\n  via 1.1.1.1\n  via 2.2.2.2\n10.1.0.0/16\n  via 3.3.3.3\n");
+
+        unsafe {
+            let ptr = netcli_run_template_json(template.as_ptr(), output.as_ptr());
+            let v: serde_json::Value = serde_json::from_str(CStr::from_ptr(ptr).to_str().unwrap()).unwrap();
+            assert_eq!(v["ok"], true, "{v}");
+            let recs = v["records"].as_array().unwrap();
+            assert_eq!(recs.len(), 2);
+            assert_eq!(recs[0]["prefix"], "10.0.0.0/8");
+            assert_eq!(recs[0]["next_hop"], serde_json::json!(["1.1.1.1", "2.2.2.2"]));
+            assert_eq!(recs[1]["vrf"], "red", "filled down from the first block");
+            assert_eq!(recs[1]["next_hop"], serde_json::json!(["3.3.3.3"]));
+
+            netcli_free(ptr);
+        }
+    }
+
+    #[test]
+    fn ffi_run_template_reports_bad_template() {
+        let template = make_c("not a template");
+        let output = make_c("text");
+
+        unsafe {
+            let ptr = netcli_run_template_json(template.as_ptr(), output.as_ptr());
+            let v: serde_json::Value = serde_json::from_str(CStr::from_ptr(ptr).to_str().unwrap()).unwrap();
+            assert_eq!(v["ok"], false);
+            assert_eq!(v["error"]["code"], "TEMPLATE_INVALID");
+
+            let ptr2 = netcli_run_template_json(std::ptr::null(), output.as_ptr());
+            let v2: serde_json::Value = serde_json::from_str(CStr::from_ptr(ptr2).to_str().unwrap()).unwrap();
+            assert_eq!(v2["error"]["code"], "INVALID_INPUT");
+
+            netcli_free(ptr);
+            netcli_free(ptr2);
+        }
+    }
+
     #[test]
     fn ffi_free_null_is_safe() {
         unsafe {
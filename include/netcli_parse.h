@@ -1,6 +1,9 @@
 #ifndef NETCLI_PARSE_H
 #define NETCLI_PARSE_H
 
+#include <stddef.h>
+#include <stdint.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
@@ -41,8 +44,88 @@ const char *netcli_parse_command_json(const char *platform,
                                       const char *output_text);
 
 /**
- * Free a string previously returned by netcli_parse_json() or
- * netcli_parse_command_json(). Passing NULL is safe (no-op).
+ * UTF-16 variant of netcli_parse_json() for callers that hold wide strings
+ * (e.g. .NET or wchar_t on Windows). Inputs are null-terminated UTF-16 in
+ * native byte order (UTF-16LE on Windows); the returned envelope is UTF-8.
+ *
+ * @return JSON envelope (null-terminated C string). The caller MUST free the
+ *         returned pointer with netcli_free().
+ */
+const char *netcli_parse_json_utf16(const uint16_t *platform,
+                                    const uint16_t *command_key,
+                                    const uint16_t *output_text);
+
+/**
+ * Run a caller-supplied TextFSM template over output_text, with no registry
+ * lookup. Uses the same engine as netcli_parse_json(), so Filldown, List,
+ * Required and Continue/Record actions behave identically, and List values
+ * come back as JSON arrays.
+ *
+ * @return {"ok":true,"records":[...]} or the usual error envelope
+ *         (null-terminated C string). The caller MUST free the returned
+ *         pointer with netcli_free().
+ */
+const char *netcli_run_template_json(const char *template_text,
+                                     const char *output_text);
+
+/**
+ * Parse many outputs of the same command in one call, compiling the template
+ * once.
+ *
+ * @param outputs_json  JSON array of raw CLI output strings.
+ *
+ * @return JSON array with one envelope per input element, in order (a
+ *         non-string element gets an error envelope in its slot). If
+ *         outputs_json is not a JSON array, a single INVALID_INPUT error
+ *         envelope is returned instead. The caller MUST free the returned
+ *         pointer with netcli_free().
+ */
+const char *netcli_parse_batch_json(const char *platform,
+                                    const char *command_key,
+                                    const char *outputs_json);
+
+/**
+ * Parse CLI output and return only the records, as a bare JSON array
+ * ("[{...}, ...]") without the envelope.
+ *
+ * @return JSON array (null-terminated C string) that the caller MUST free
+ *         with netcli_free(), or NULL on error. On NULL, call
+ *         netcli_last_error_code() for the reason.
+ */
+const char *netcli_parse_records_json(const char *platform,
+                                      const char *command_key,
+                                      const char *output_text);
+
+/**
+ * netcli_parse_json() into a caller-owned buffer; no netcli_free() needed.
+ *
+ * @param buf      Destination for the envelope and its terminating NUL.
+ * @param buf_len  Size of buf in bytes.
+ *
+ * @return Bytes written, excluding the NUL. If buf is too small (or NULL),
+ *         nothing is written and the negated required size, including the
+ *         NUL, is returned so the caller can resize and retry.
+ */
+ptrdiff_t netcli_parse_into_buffer(const char *platform,
+                                   const char *command_key,
+                                   const char *output_text,
+                                   char *buf,
+                                   size_t buf_len);
+
+/**
+ * Error code of the last failed netcli_parse_records_json() call on the
+ * calling thread (e.g. "TEMPLATE_NOT_FOUND"), or NULL if it succeeded.
+ * The string is owned by the library and stays valid until the next
+ * netcli_parse_records_json() call on the same thread; do NOT free it.
+ */
+const char *netcli_last_error_code(void);
+
+/**
+ * Free a string previously returned by netcli_parse_json(),
+ * netcli_parse_command_json(), netcli_parse_json_utf16(),
+ * netcli_run_template_json(), netcli_parse_batch_json() or
+ * netcli_parse_records_json().
+ * Passing NULL is safe (no-op).
  */
 void netcli_free(const char *s);
 